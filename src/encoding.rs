@@ -0,0 +1,108 @@
+//! The character encodings.
+
+use std::io;
+
+/// Decode a document into UTF-8 based on its byte order mark or, in its
+/// absence, on the encoding given in its XML declaration.
+///
+/// UTF-8, UTF-16 with a byte order mark, US-ASCII, ISO-8859-1, and
+/// Windows-1252 are supported. A document without any indication of its
+/// encoding is assumed to be in UTF-8.
+pub(crate) fn decode(bytes: &[u8]) -> io::Result<String> {
+    if let Some(bytes) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return decode_utf8(bytes);
+    }
+    if let Some(bytes) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return decode_utf16(bytes, u16::from_le_bytes);
+    }
+    if let Some(bytes) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return decode_utf16(bytes, u16::from_be_bytes);
+    }
+    let label = match declared(bytes) {
+        Some(label) => label.to_ascii_lowercase(),
+        _ => return decode_utf8(bytes),
+    };
+    match label.as_str() {
+        "utf-8" | "utf8" | "us-ascii" | "ascii" => decode_utf8(bytes),
+        "iso-8859-1" | "iso8859-1" | "iso_8859-1" | "latin1" | "l1" => {
+            Ok(bytes.iter().map(|&byte| byte as char).collect())
+        }
+        "windows-1252" | "cp1252" => Ok(bytes.iter().map(|&byte| windows_1252(byte)).collect()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("found an unsupported encoding '{}'", label),
+        )),
+    }
+}
+
+fn declared(bytes: &[u8]) -> Option<String> {
+    let bytes = bytes.strip_prefix(b"<?xml")?;
+    let end = bytes.windows(2).position(|pair| pair == b"?>")?;
+    let declaration = String::from_utf8_lossy(&bytes[..end]);
+    let (_, rest) = declaration.split_once("encoding")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let (label, _) = rest[1..].split_once(quote)?;
+    Some(label.into())
+}
+
+fn decode_utf8(bytes: &[u8]) -> io::Result<String> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+fn decode_utf16(bytes: &[u8], convert: fn([u8; 2]) -> u16) -> io::Result<String> {
+    let units = bytes
+        .chunks(2)
+        .map(|pair| match pair {
+            &[first, second] => Ok(convert([first, second])),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "found an odd number of bytes",
+            )),
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    String::from_utf16(&units).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+// https://encoding.spec.whatwg.org/index-windows-1252.txt
+fn windows_1252(byte: u8) -> char {
+    const TABLE: [char; 32] = [
+        '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}',
+        '\u{2021}', '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}',
+        '\u{8F}', '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}',
+        '\u{2014}', '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}',
+        '\u{178}',
+    ];
+    match byte {
+        0x80..=0x9F => TABLE[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode;
+
+    #[test]
+    fn decode_declared() {
+        let mut bytes = b"<?xml version='1.0' encoding=\"ISO-8859-1\"?><text>caf".to_vec();
+        bytes.extend_from_slice(&[0xE9, 0x80]);
+        bytes.extend_from_slice(b"</text>");
+        assert!(decode(&bytes)
+            .unwrap()
+            .ends_with("<text>caf\u{E9}\u{80}</text>"));
+
+        let bytes = b"<?xml version='1.0' encoding='windows-1252'?>\x80";
+        assert!(decode(bytes).unwrap().ends_with('\u{20AC}'));
+
+        assert!(decode(b"<?xml encoding='koi8-r'?>").is_err());
+    }
+
+    #[test]
+    fn decode_byte_order_mark() {
+        assert_eq!(decode(b"\xEF\xBB\xBF<a/>").unwrap(), "<a/>");
+        assert_eq!(decode(b"\xFF\xFE<\x00a\x00/\x00>\x00").unwrap(), "<a/>");
+        assert_eq!(decode(b"\xFE\xFF\x00<\x00a\x00/\x00>").unwrap(), "<a/>");
+    }
+}
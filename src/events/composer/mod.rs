@@ -3,7 +3,7 @@
 use std::io;
 use std::io::Write;
 
-use crate::events::Event;
+use crate::events::{CompactEvent, Event};
 
 #[doc(hidden)]
 pub use self::writer::Writer;
@@ -12,17 +12,132 @@ mod writer;
 
 pub struct Composer<T: Write> {
     writer: Writer<T>,
+    flush_every: Option<usize>,
+    pending: usize,
+}
+
+/// Options of a composer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Options {
+    /// Skip text events that are empty or consist only of whitespace, except
+    /// inside elements whose whitespace is significant, such as `style`.
+    pub skip_blank_text: bool,
+    /// Wrap the attributes of tags whose lines would otherwise be longer than
+    /// this number of characters, aligning continuation lines with the first
+    /// attribute. A single attribute exceeding the width is not split.
+    pub max_line_width: Option<usize>,
+    /// Write namespace declarations, that is, `xmlns` and `xmlns:*`
+    /// attributes, before all other attributes as Canonical XML does. Each
+    /// group is still sorted by name.
+    pub namespaces_first: bool,
+    /// Write presence-only attributes, which are assigned with `set_flag`,
+    /// as bare names (eg. `hidden`) instead of with their names as values
+    /// (eg. `hidden="hidden"`). The former is not well-formed XML.
+    pub bare_flags: bool,
+    /// Write hexadecimal colors in paint attributes, such as `fill` and
+    /// `stroke`, in lowercase (eg. `#ABC` as `#abc`) so that the letter case
+    /// of colors is consistent.
+    pub lowercase_hex_colors: bool,
+    /// Indent each line by this number of spaces per level of nesting. The
+    /// continuation lines of wrapped attributes are aligned relative to the
+    /// indentation of their element.
+    pub indent: Option<usize>,
+    /// Keep track of the open elements and fail with an error of kind
+    /// `InvalidInput` when an end tag does not match the most recent start
+    /// tag, which is then not written. Documents are always balanced, so it
+    /// is only useful when writing events one by one.
+    pub check_nesting: bool,
 }
 
 impl<T: Write> Composer<T> {
     #[inline]
     pub fn new(destination: T) -> Self {
+        Composer::with_options(destination, Options::default())
+    }
+
+    /// Create a composer with options.
+    #[inline]
+    pub fn with_options(destination: T, options: Options) -> Self {
         Composer {
-            writer: Writer::new(destination),
+            writer: Writer::with_options(destination, options),
+            flush_every: None,
+            pending: 0,
         }
     }
 
+    /// Flush the destination after every given number of events, which keeps
+    /// buffering bounded when streaming.
+    #[inline]
+    pub fn flush_every(mut self, events: usize) -> Self {
+        self.flush_every = Some(events.max(1));
+        self
+    }
+
+    #[inline]
     pub fn write_event(&mut self, event: &Event) -> io::Result<()> {
-        self.writer.write_event(event)
+        self.write_compact_event(event.into())
+    }
+
+    /// Write an event borrowing its attributes.
+    pub fn write_compact_event(&mut self, event: CompactEvent) -> io::Result<()> {
+        self.writer.write_compact_event(event)?;
+        if let Some(events) = self.flush_every {
+            self.pending += 1;
+            if self.pending >= events {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flush the destination.
+    #[inline]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.pending = 0;
+        self.writer.flush()
+    }
+
+    /// Return the number of bytes written to the destination so far.
+    #[inline]
+    pub fn bytes_written(&self) -> usize {
+        self.writer.bytes_written()
+    }
+
+    /// Forget the events written so far, so that the composer can be reused
+    /// for another document as if it were new.
+    ///
+    /// The destination is neither flushed nor rewound, and the number of
+    /// bytes written is kept.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.writer.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Composer;
+    use crate::node::element::Circle;
+    use crate::Document;
+
+    #[test]
+    fn reset() {
+        let documents = [Document::new().add(Circle::new()), Document::new()];
+
+        let mut output = Vec::new();
+        {
+            let mut composer = Composer::new(&mut output);
+            for document in &documents {
+                composer.reset();
+                for event in document.to_compact_events() {
+                    composer.write_compact_event(event).unwrap();
+                }
+            }
+        }
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "<svg>\n<circle/>\n</svg><svg/>",
+        );
     }
 }
@@ -1,16 +1,48 @@
+use std::borrow::Cow;
 use std::io;
 use std::io::Write;
 
-use crate::events::Event;
-use crate::node::element::tag::Type;
-use crate::node::{Attributes, Value};
+use super::Options;
+use crate::events::{CompactEvent, Event};
+use crate::node::element::tag::{self, Type};
+use crate::node::{Attributes, Color, Quote, Value};
+use crate::xml;
 
 pub struct Writer<T>
 where
     T: Write,
 {
-    destination: T,
+    destination: Counter<T>,
     initial_event_written: bool,
+    preserving: usize,
+    depth: usize,
+    open: Vec<String>,
+    options: Options,
+}
+
+/// Elements whose content is written exactly as is, without newlines inserted
+/// between events.
+const WHITESPACE_SENSITIVE: &[&str] = &[tag::Style, tag::Script, tag::ForeignObject, tag::Metadata];
+
+struct Counter<T> {
+    inner: T,
+    count: usize,
+}
+
+impl<T> Write for Counter<T>
+where
+    T: Write,
+{
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        let count = self.inner.write(buffer)?;
+        self.count += count;
+        Ok(count)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
 }
 
 impl<T> Writer<T>
@@ -19,63 +51,183 @@ where
 {
     #[inline]
     pub fn new(destination: T) -> Self {
+        Self::with_options(destination, Options::default())
+    }
+
+    #[inline]
+    pub fn with_options(destination: T, options: Options) -> Self {
         Self {
-            destination,
+            destination: Counter {
+                inner: destination,
+                count: 0,
+            },
             initial_event_written: false,
+            preserving: 0,
+            depth: 0,
+            open: Vec::new(),
+            options,
         }
     }
 
-    fn write_attribute(&mut self, name: &str, value: &Value) -> io::Result<()> {
-        match (value.contains('\''), value.contains('"')) {
-            (true, false) | (false, false) => {
-                write!(self.destination, r#" {}="{}""#, name, value)?;
-            }
-            (false, true) => {
-                write!(self.destination, r#" {}='{}'"#, name, value)?;
-            }
-            _ => {}
+    #[inline]
+    pub fn bytes_written(&self) -> usize {
+        self.destination.count
+    }
+
+    #[inline]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.destination.flush()
+    }
+
+    pub fn reset(&mut self) {
+        self.initial_event_written = false;
+        self.preserving = 0;
+        self.depth = 0;
+        self.open.clear();
+    }
+
+    fn format_attribute(&self, name: &str, value: &Value) -> String {
+        if self.options.bare_flags && value.is_flag() {
+            return format!(" {}", name);
         }
-        Ok(())
+        let normalized = match self.options.lowercase_hex_colors {
+            true => normalize_hex_color(name, value),
+            false => None,
+        };
+        let value = normalized.as_ref().unwrap_or(value);
+        let quote = match value.quote() {
+            Some(quote) => quote,
+            None if value.contains('"') && !value.contains('\'') => Quote::Single,
+            None => Quote::Double,
+        };
+        let quote = quote.as_char();
+        let (before, after) = value.spacing().unwrap_or((" ", ""));
+        let value = if value.is_raw() {
+            Cow::Borrowed(&**value)
+        } else {
+            xml::escape_attribute(value, quote)
+        };
+        format!("{}{}={}{}{}{}", before, name, quote, value, quote, after)
     }
 
-    fn write_attributes(&mut self, attributes: &Attributes) -> io::Result<()> {
+    fn write_attributes(
+        &mut self,
+        name: &str,
+        attributes: &Attributes,
+        ending: &str,
+    ) -> io::Result<()> {
         let mut attributes = attributes.iter().collect::<Vec<_>>();
-        attributes.sort_by_key(|pair| pair.0.as_str());
-        for (name, value) in attributes {
-            self.write_attribute(name, value)?;
+        // Attributes read with their spacing keep their order in the content.
+        if self.options.namespaces_first {
+            attributes.sort_by_key(|pair| {
+                (
+                    !is_namespace_declaration(pair.0),
+                    pair.1.order(),
+                    pair.0.as_str(),
+                )
+            });
+        } else {
+            attributes.sort_by_key(|pair| (pair.1.order(), pair.0.as_str()));
         }
-        Ok(())
+        let indent = self.margin() + 1 + name.chars().count();
+        let mut column = indent;
+        let count = attributes.len();
+        for (i, (name, value)) in attributes.into_iter().enumerate() {
+            let attribute = self.format_attribute(name, value);
+            let mut width = attribute.chars().count();
+            if i + 1 == count {
+                width += ending.len();
+            }
+            if let Some(max_line_width) = self.options.max_line_width {
+                if i > 0 && column + width > max_line_width {
+                    write!(self.destination, "\n{:indent$}", "", indent = indent)?;
+                    column = indent;
+                }
+            }
+            write!(self.destination, "{}", attribute)?;
+            column += width;
+        }
+        write!(self.destination, "{}", ending)
     }
 
     fn initial_newline(&mut self) -> io::Result<()> {
+        if self.preserving > 0 {
+            return Ok(());
+        }
         if self.initial_event_written {
             writeln!(self.destination)?;
         } else {
             self.initial_event_written = true;
         }
-        Ok(())
+        write!(self.destination, "{:margin$}", "", margin = self.margin())
+    }
+
+    fn margin(&self) -> usize {
+        match self.options.indent {
+            Some(indent) if self.preserving == 0 => self.depth * indent,
+            _ => 0,
+        }
     }
 
     fn write_start_tag(&mut self, name: &str, attributes: &Attributes) -> io::Result<()> {
         self.initial_newline()?;
         write!(self.destination, "<{}", name)?;
-        self.write_attributes(attributes)?;
-        write!(self.destination, ">")
+        self.write_attributes(name, attributes, ">")?;
+        self.depth += 1;
+        if self.options.check_nesting {
+            self.open.push(name.into());
+        }
+        if self.preserving > 0 || WHITESPACE_SENSITIVE.contains(&name) {
+            self.preserving += 1;
+        }
+        Ok(())
     }
 
     fn write_empty_tag(&mut self, name: &str, attributes: &Attributes) -> io::Result<()> {
         self.initial_newline()?;
         write!(self.destination, "<{}", name)?;
-        self.write_attributes(attributes)?;
-        write!(self.destination, "/>")
+        self.write_attributes(name, attributes, "/>")
     }
 
     fn write_end_tag(&mut self, name: &str) -> io::Result<()> {
-        self.initial_newline()?;
+        if self.options.check_nesting {
+            match self.open.last() {
+                Some(open) if open == name => {
+                    self.open.pop();
+                }
+                open => {
+                    let message = match open {
+                        Some(open) => format!(
+                            "found an end tag '{}' not matching the start tag '{}'",
+                            name, open,
+                        ),
+                        _ => format!("found an end tag '{}' without a start tag", name),
+                    };
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, message));
+                }
+            }
+        }
+        self.depth = self.depth.saturating_sub(1);
+        if self.preserving > 0 {
+            self.preserving -= 1;
+        } else {
+            self.initial_newline()?;
+        }
         write!(self.destination, "</{}>", name)
     }
 
     fn write_text(&mut self, content: &str) -> io::Result<()> {
+        if self.options.skip_blank_text
+            && self.preserving == 0
+            && content.chars().all(char::is_whitespace)
+        {
+            return Ok(());
+        }
+        self.initial_newline()?;
+        write!(self.destination, "{}", xml::escape_text(content))
+    }
+
+    fn write_raw_text(&mut self, content: &str) -> io::Result<()> {
         self.initial_newline()?;
         write!(self.destination, "{}", content)
     }
@@ -100,28 +252,63 @@ where
         write!(self.destination, "<?{}?>", content)
     }
 
+    #[inline]
     pub fn write_event(&mut self, event: &Event) -> io::Result<()> {
+        self.write_compact_event(event.into())
+    }
+
+    pub fn write_compact_event(&mut self, event: CompactEvent) -> io::Result<()> {
         match event {
-            Event::Tag(name, Type::Start, attributes) => self.write_start_tag(name, attributes),
-            Event::Tag(name, Type::Empty, attributes) => self.write_empty_tag(name, attributes),
-            Event::Tag(name, Type::End, _) => self.write_end_tag(name),
-            Event::Text(content) => self.write_text(content),
-            Event::Comment(content) => self.write_comment(content),
-            Event::UnpaddedComment(content) => self.write_unpadded_comment(content),
-            Event::Declaration(content) => self.write_declaration(content),
-            Event::Instruction(content) => self.write_instruction(content),
+            CompactEvent::Tag(name, Type::Start, attributes) => {
+                self.write_start_tag(name, attributes)
+            }
+            CompactEvent::Tag(name, Type::Empty, attributes) => {
+                self.write_empty_tag(name, attributes)
+            }
+            CompactEvent::Tag(name, Type::End, _) => self.write_end_tag(name),
+            CompactEvent::Text(content) => self.write_text(content),
+            CompactEvent::RawText(content) => self.write_raw_text(content),
+            CompactEvent::Comment(content) => self.write_comment(content),
+            CompactEvent::UnpaddedComment(content) => self.write_unpadded_comment(content),
+            CompactEvent::Declaration(content) => self.write_declaration(content),
+            CompactEvent::Instruction(content) => self.write_instruction(content),
         }
     }
 }
 
+fn is_namespace_declaration(name: &str) -> bool {
+    name == "xmlns" || name.starts_with("xmlns:")
+}
+
+fn normalize_hex_color(name: &str, value: &Value) -> Option<Value> {
+    const PAINTS: &[&str] = &[
+        "color",
+        "fill",
+        "flood-color",
+        "lighting-color",
+        "stop-color",
+        "stroke",
+    ];
+    if !PAINTS.contains(&name) || !value.trim_start().starts_with('#') {
+        return None;
+    }
+    match Color::parse(value) {
+        Ok(_) if value.bytes().any(|byte| byte.is_ascii_uppercase()) => {
+            Some(value.to_ascii_lowercase())
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::io;
 
-    use super::Writer;
+    use super::{Options, Writer};
     use crate::events::Event;
     use crate::node::element::tag::Type;
-    use crate::node::Value;
+    use crate::node::{Quote, Value};
 
     fn events_to_string(events: &[Event]) -> String {
         let mut output = Vec::new();
@@ -164,7 +351,178 @@ mod tests {
 
         assert_eq!(
             events_to_string(&[foo]),
-            r#"<foo d='"double"' s="'single'"/>"#
+            r#"<foo d='"double"' m="&quot;mixed'" s="'single'"/>"#
+        );
+    }
+
+    #[test]
+    fn event_display_wrapped() {
+        let mut attributes = HashMap::new();
+        for name in ["a", "b", "c", "d", "e", "f", "g", "h"].iter() {
+            attributes.insert(name.to_string(), Value::from("value"));
+        }
+        let events = [
+            Event::Tag("rect", Type::Empty, attributes.clone()),
+            Event::Tag("g", Type::Start, attributes),
+        ];
+
+        let mut output = Vec::new();
+        let options = Options {
+            max_line_width: Some(30),
+            ..Default::default()
+        };
+        let mut writer = Writer::with_options(&mut output, options);
+        for event in &events {
+            writer.write_event(event).unwrap();
+        }
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.lines().all(|line| line.len() <= 30));
+        assert_eq!(
+            output,
+            "<rect a=\"value\" b=\"value\"\n\
+             \x20     c=\"value\" d=\"value\"\n\
+             \x20     e=\"value\" f=\"value\"\n\
+             \x20     g=\"value\" h=\"value\"/>\n\
+             <g a=\"value\" b=\"value\"\n\
+             \x20  c=\"value\" d=\"value\"\n\
+             \x20  e=\"value\" f=\"value\"\n\
+             \x20  g=\"value\" h=\"value\">"
+        );
+    }
+
+    #[test]
+    fn event_display_indent() {
+        let mut attributes = HashMap::new();
+        for name in ["a", "b", "c", "d"].iter() {
+            attributes.insert(name.to_string(), Value::from("value"));
+        }
+        let events = vec![
+            Event::Tag("svg", Type::Start, HashMap::new()),
+            Event::Tag("g", Type::Start, HashMap::new()),
+            Event::Tag("rect", Type::Empty, attributes),
+            Event::Tag("g", Type::End, HashMap::new()),
+            Event::Tag("svg", Type::End, HashMap::new()),
+        ];
+
+        let mut output = Vec::new();
+        let options = Options {
+            max_line_width: Some(30),
+            indent: Some(2),
+            ..Default::default()
+        };
+        let mut writer = Writer::with_options(&mut output, options);
+        for event in &events {
+            writer.write_event(event).unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "<svg>\n\
+             \x20 <g>\n\
+             \x20   <rect a=\"value\" b=\"value\"\n\
+             \x20         c=\"value\"\n\
+             \x20         d=\"value\"/>\n\
+             \x20 </g>\n\
+             </svg>",
+        );
+    }
+
+    #[test]
+    fn event_display_check_nesting() {
+        let options = Options {
+            check_nesting: true,
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        let mut writer = Writer::with_options(&mut output, options);
+        writer
+            .write_event(&Event::Tag("a", Type::Start, HashMap::new()))
+            .unwrap();
+        let error = writer
+            .write_event(&Event::Tag("b", Type::End, HashMap::new()))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        writer
+            .write_event(&Event::Tag("a", Type::End, HashMap::new()))
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "<a>\n</a>");
+
+        let mut output = Vec::new();
+        let mut writer = Writer::new(&mut output);
+        writer
+            .write_event(&Event::Tag("a", Type::Start, HashMap::new()))
+            .unwrap();
+        writer
+            .write_event(&Event::Tag("b", Type::End, HashMap::new()))
+            .unwrap();
+    }
+
+    #[test]
+    fn event_display_namespaces_first() {
+        let mut attributes = HashMap::new();
+        for name in ["fill", "xmlns", "d", "xmlns:xlink", "xlink:href"].iter() {
+            attributes.insert(name.to_string(), Value::from("x"));
+        }
+        let event = Event::Tag("path", Type::Empty, attributes);
+
+        assert_eq!(
+            events_to_string(std::slice::from_ref(&event)),
+            r#"<path d="x" fill="x" xlink:href="x" xmlns="x" xmlns:xlink="x"/>"#,
+        );
+
+        let mut output = Vec::new();
+        let options = Options {
+            namespaces_first: true,
+            ..Default::default()
+        };
+        Writer::with_options(&mut output, options)
+            .write_event(&event)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            r#"<path xmlns="x" xmlns:xlink="x" d="x" fill="x" xlink:href="x"/>"#,
+        );
+    }
+
+    #[test]
+    fn event_display_lowercase_hex_colors() {
+        let mut attributes = HashMap::new();
+        attributes.insert(
+            "fill".to_string(),
+            Value::from("#ABC").with_quote(Quote::Single),
+        );
+        attributes.insert(
+            "stroke".to_string(),
+            Value::from("#A0B0C0").with_spacing("\n", " "),
+        );
+        attributes.insert("href".to_string(), Value::from("#ABC"));
+        let event = Event::Tag("use", Type::Empty, attributes);
+
+        let mut output = Vec::new();
+        let options = Options {
+            lowercase_hex_colors: true,
+            ..Default::default()
+        };
+        Writer::with_options(&mut output, options)
+            .write_event(&event)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "<use fill='#abc' href=\"#ABC\"\nstroke=\"#a0b0c0\" />",
+        );
+    }
+
+    #[test]
+    fn event_display_raw() {
+        let mut foo_attributes = HashMap::new();
+        foo_attributes.insert("a".into(), Value::raw("&amp;"));
+        foo_attributes.insert("b".into(), Value::from("&amp;"));
+        let foo = Event::Tag("foo", Type::Empty, foo_attributes);
+
+        assert_eq!(
+            events_to_string(&[foo]),
+            r#"<foo a="&amp;" b="&amp;amp;"/>"#
         );
     }
 
@@ -178,13 +536,56 @@ mod tests {
 
         assert_eq!(
             events_to_string(&[style, style_text, style_end]),
-            "<style>\n\
-             * { font-family: foo; }\n\
-             </style>\
-             "
+            "<style>* { font-family: foo; }</style>"
+        );
+    }
+
+    #[test]
+    fn style_display_whitespace() {
+        let group = Event::Tag("g", Type::Start, HashMap::new());
+        let style = Event::Tag("style", Type::Start, HashMap::new());
+        let style_text = Event::Text("\n  .a { fill: red; }\n\n  .b { fill: blue; }\n");
+        let style_end = Event::Tag("style", Type::End, HashMap::new());
+        let group_end = Event::Tag("g", Type::End, HashMap::new());
+
+        assert_eq!(
+            events_to_string(&[group, style, style_text, style_end, group_end]),
+            "<g>\n\
+             <style>\n  .a { fill: red; }\n\n  .b { fill: blue; }\n</style>\n\
+             </g>"
         );
     }
 
+    #[test]
+    fn text_display_skip_blank() {
+        let events = [
+            Event::Tag("g", Type::Start, HashMap::new()),
+            Event::Text("\n  "),
+            Event::Tag("path", Type::Empty, HashMap::new()),
+            Event::Text(""),
+            Event::Tag("g", Type::End, HashMap::new()),
+        ];
+
+        assert_eq!(events_to_string(&events), "<g>\n\n  \n<path/>\n\n</g>");
+
+        let mut output = Vec::new();
+        let options = Options {
+            skip_blank_text: true,
+            ..Default::default()
+        };
+        let mut writer = Writer::with_options(&mut output, options);
+        for event in &events {
+            writer.write_event(event).unwrap();
+        }
+        assert_eq!(String::from_utf8(output).unwrap(), "<g>\n<path/>\n</g>");
+    }
+
+    #[test]
+    fn text_display() {
+        let text = Event::Text("a < b & c ]]> d");
+        assert_eq!(events_to_string(&[text]), "a &lt; b &amp; c ]]&gt; d");
+    }
+
     #[test]
     fn comment_display() {
         let comment = Event::Comment("valid");
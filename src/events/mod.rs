@@ -1,9 +1,37 @@
-use crate::node::element::tag::Type;
+use crate::node::element::tag::{self, Type};
 use crate::node::Attributes;
 
 pub mod composer;
 pub mod parser;
 
+/// Elements whose content can be passed through as a single raw text event,
+/// which keeps foreign markup such as RDF byte for byte; see
+/// `Parser::opaque_metadata`.
+pub(crate) const OPAQUE: &[&str] = &[tag::Metadata];
+
+/// An event borrowing the attributes of a tag instead of owning them.
+///
+/// It mirrors `Event` and is what the nodes produce for composing, which
+/// avoids copying attributes. End tags refer to the attributes of their start
+/// tags, which are to be ignored.
+#[derive(Clone, Copy, Debug)]
+pub enum CompactEvent<'l> {
+    /// A tag.
+    Tag(&'l str, Type, &'l Attributes),
+    /// A text.
+    Text(&'l str),
+    /// A text written as is, without escaping.
+    RawText(&'l str),
+    /// A padded comment (eg. `<!-- foo -->`).
+    Comment(&'l str),
+    /// An unpadded comment (eg. `<!--foo-->`).
+    UnpaddedComment(&'l str),
+    /// A declaration.
+    Declaration(&'l str),
+    /// An instruction.
+    Instruction(&'l str),
+}
+
 /// An event.
 #[derive(Debug)]
 pub enum Event<'l> {
@@ -11,6 +39,8 @@ pub enum Event<'l> {
     Tag(&'l str, Type, Attributes),
     /// A text.
     Text(&'l str),
+    /// A text written as is, without escaping.
+    RawText(&'l str),
     /// A padded comment (eg. `<!-- foo -->`).
     Comment(&'l str),
     /// An unpadded comment (eg. `<!--foo-->`).
@@ -21,12 +51,141 @@ pub enum Event<'l> {
     Instruction(&'l str),
 }
 
+/// An event owning its content, which makes it independent of the source it
+/// was produced from.
+#[derive(Clone, Debug)]
+pub enum OwnedEvent {
+    /// A tag.
+    Tag(String, Type, Attributes),
+    /// A text.
+    Text(String),
+    /// A text written as is, without escaping.
+    RawText(String),
+    /// A padded comment (eg. `<!-- foo -->`).
+    Comment(String),
+    /// An unpadded comment (eg. `<!--foo-->`).
+    UnpaddedComment(String),
+    /// A declaration.
+    Declaration(String),
+    /// An instruction.
+    Instruction(String),
+}
+
+impl<'l> Event<'l> {
+    /// Check if the event is a start tag.
+    #[inline]
+    pub fn is_start_tag(&self) -> bool {
+        matches!(self, Event::Tag(_, Type::Start, _))
+    }
+
+    /// Check if the event is an end tag.
+    #[inline]
+    pub fn is_end_tag(&self) -> bool {
+        matches!(self, Event::Tag(_, Type::End, _))
+    }
+
+    /// Check if the event is an empty tag.
+    #[inline]
+    pub fn is_empty_tag(&self) -> bool {
+        matches!(self, Event::Tag(_, Type::Empty, _))
+    }
+
+    /// Return the name of the tag if the event is a tag.
+    #[inline]
+    pub fn tag_name(&self) -> Option<&'l str> {
+        match self {
+            Event::Tag(name, _, _) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Return the attributes of the tag if the event is a tag.
+    #[inline]
+    pub fn attributes(&self) -> Option<&Attributes> {
+        match self {
+            Event::Tag(_, _, attributes) => Some(attributes),
+            _ => None,
+        }
+    }
+}
+
+impl<'l> From<&'l Event<'l>> for CompactEvent<'l> {
+    fn from(event: &'l Event<'l>) -> Self {
+        match event {
+            Event::Tag(name, kind, attributes) => CompactEvent::Tag(name, *kind, attributes),
+            Event::Text(content) => CompactEvent::Text(content),
+            Event::RawText(content) => CompactEvent::RawText(content),
+            Event::Comment(content) => CompactEvent::Comment(content),
+            Event::UnpaddedComment(content) => CompactEvent::UnpaddedComment(content),
+            Event::Declaration(content) => CompactEvent::Declaration(content),
+            Event::Instruction(content) => CompactEvent::Instruction(content),
+        }
+    }
+}
+
+impl<'l> From<CompactEvent<'l>> for OwnedEvent {
+    fn from(event: CompactEvent<'l>) -> Self {
+        match event {
+            CompactEvent::Tag(name, Type::End, _) => {
+                OwnedEvent::Tag(name.into(), Type::End, Attributes::new())
+            }
+            CompactEvent::Tag(name, kind, attributes) => {
+                OwnedEvent::Tag(name.into(), kind, attributes.clone())
+            }
+            CompactEvent::Text(content) => OwnedEvent::Text(content.into()),
+            CompactEvent::RawText(content) => OwnedEvent::RawText(content.into()),
+            CompactEvent::Comment(content) => OwnedEvent::Comment(content.into()),
+            CompactEvent::UnpaddedComment(content) => OwnedEvent::UnpaddedComment(content.into()),
+            CompactEvent::Declaration(content) => OwnedEvent::Declaration(content.into()),
+            CompactEvent::Instruction(content) => OwnedEvent::Instruction(content.into()),
+        }
+    }
+}
+
+impl<'l> From<&'l OwnedEvent> for CompactEvent<'l> {
+    fn from(event: &'l OwnedEvent) -> Self {
+        match event {
+            OwnedEvent::Tag(name, kind, attributes) => CompactEvent::Tag(name, *kind, attributes),
+            OwnedEvent::Text(content) => CompactEvent::Text(content),
+            OwnedEvent::RawText(content) => CompactEvent::RawText(content),
+            OwnedEvent::Comment(content) => CompactEvent::Comment(content),
+            OwnedEvent::UnpaddedComment(content) => CompactEvent::UnpaddedComment(content),
+            OwnedEvent::Declaration(content) => CompactEvent::Declaration(content),
+            OwnedEvent::Instruction(content) => CompactEvent::Instruction(content),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs, io};
 
     use super::composer::Composer;
     use super::parser::Parser;
+    use super::Event;
+    use crate::xml;
+
+    #[test]
+    fn tag_predicates() {
+        let events = Parser::new("<a x='1'><b/>c</a>")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(events[0].is_start_tag());
+        assert!(!events[0].is_empty_tag());
+        assert_eq!(events[0].tag_name(), Some("a"));
+        assert_eq!(&*events[0].attributes().unwrap()["x"], "1");
+
+        assert!(events[1].is_empty_tag());
+        assert_eq!(events[1].tag_name(), Some("b"));
+
+        assert!(!events[2].is_start_tag());
+        assert_eq!(events[2].tag_name(), None);
+        assert!(events[2].attributes().is_none());
+
+        assert!(events[3].is_end_tag());
+        assert!(events[3].attributes().unwrap().is_empty());
+    }
 
     #[test]
     fn identity_iterator() {
@@ -46,4 +205,82 @@ mod tests {
         let composed = String::from_utf8(destination).unwrap();
         assert_eq!(contents, composed);
     }
+
+    #[test]
+    fn identity_quotes() {
+        let mut destination = Vec::new();
+        let mut composer = Composer::new(&mut destination);
+
+        let contents = r#"<a href='x' title="y"/>"#;
+        Parser::new(contents)
+            .map(|event| event.unwrap())
+            .try_for_each(|event| composer.write_event(&event))
+            .unwrap();
+
+        let composed = String::from_utf8(destination).unwrap();
+        assert_eq!(contents, composed);
+    }
+
+    #[test]
+    fn identity_text_escaping() {
+        let mut destination = Vec::new();
+        let mut composer = Composer::new(&mut destination);
+
+        let content = "a ]]> b";
+        composer.write_event(&Event::Text(content)).unwrap();
+
+        let composed = String::from_utf8(destination).unwrap();
+        match Parser::new(&composed).next().unwrap().unwrap() {
+            Event::Text(value) => assert_eq!(xml::unescape_lenient(value), content),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn bytes_written() {
+        let mut destination = Vec::new();
+        let mut composer = Composer::new(&mut destination);
+
+        let contents = fs::read_to_string("tests/fixtures/benton_composer_formatted.svg").unwrap();
+        Parser::new(&contents)
+            .map(|event| event.unwrap())
+            .try_for_each(|event| composer.write_event(&event))
+            .unwrap();
+
+        let count = composer.bytes_written();
+        assert!(count > 0);
+        assert_eq!(count, destination.len());
+    }
+
+    #[test]
+    fn flush_every() {
+        struct Recorder {
+            written: usize,
+            flushes: Vec<usize>,
+        }
+
+        impl io::Write for Recorder {
+            fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+                self.written += buffer.len();
+                Ok(buffer.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                self.flushes.push(self.written);
+                Ok(())
+            }
+        }
+
+        let mut recorder = Recorder {
+            written: 0,
+            flushes: Vec::new(),
+        };
+        let mut composer = Composer::new(&mut recorder).flush_every(2);
+        Parser::new("<a><b/><c/><d/><e/></a>")
+            .map(|event| event.unwrap())
+            .try_for_each(|event| composer.write_event(&event))
+            .unwrap();
+
+        assert_eq!(recorder.flushes, vec![8, 18, 28]);
+    }
 }
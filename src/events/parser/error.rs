@@ -7,6 +7,7 @@ use std::{error, fmt};
 pub struct Error {
     line: usize,
     column: usize,
+    offset: Option<usize>,
     message: String,
 }
 
@@ -17,9 +18,51 @@ impl Error {
         Error {
             line,
             column,
+            offset: None,
             message: message.into(),
         }
     }
+
+    /// Set the offset in bytes.
+    #[inline]
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Translate the position from a part of some content to the whole
+    /// content, given the position and offset of the first character of
+    /// the part.
+    pub(crate) fn within(mut self, (line, column): (usize, usize), offset: usize) -> Self {
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line = match self.line {
+            0 => line,
+            relative => line + relative - 1,
+        };
+        self.offset = Some(offset + self.offset.unwrap_or(0));
+        self
+    }
+
+    /// Return the line, starting at one, or zero if unknown.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the column in characters, starting at one, or zero if unknown.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Return the offset in bytes from the start of the content, which can be
+    /// used for indexing the content, if known.
+    #[inline]
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
 }
 
 impl error::Error for Error {
@@ -0,0 +1,30 @@
+//! The handlers.
+
+use crate::node::Attributes;
+
+/// A receiver of the events of a parser, which are pushed to it by
+/// `Parser::drive`.
+///
+/// All methods do nothing by default, so only the ones of interest need to be
+/// implemented. An empty tag results in `start_element` followed by
+/// `end_element`.
+pub trait Handler<'l> {
+    /// Handle a start or empty tag.
+    fn start_element(&mut self, _name: &'l str, _attributes: &Attributes) {}
+
+    /// Handle an end or empty tag.
+    fn end_element(&mut self, _name: &'l str) {}
+
+    /// Handle a text.
+    fn text(&mut self, _content: &'l str) {}
+
+    /// Handle a comment, which is either padded (eg. `<!-- foo -->`) or not
+    /// (eg. `<!--foo-->`).
+    fn comment(&mut self, _content: &'l str, _padded: bool) {}
+
+    /// Handle a declaration.
+    fn declaration(&mut self, _content: &'l str) {}
+
+    /// Handle an instruction.
+    fn instruction(&mut self, _content: &'l str) {}
+}
@@ -1,19 +1,38 @@
 //! The parser.
 
-use crate::events::Event;
-use crate::node::element::tag::Tag;
+use std::ops::Range;
+
+use crate::events::{Event, OPAQUE};
+use crate::node::element::tag::{self, Tag, Type};
 
 pub use self::error::Error;
+pub use self::handler::Handler;
 
-#[doc(hidden)]
-pub use self::reader::Reader;
+pub(crate) use self::reader::Reader;
 
 mod error;
+mod handler;
 mod reader;
 
 /// A parser.
 pub struct Parser<'l> {
     reader: Reader<'l>,
+    peeked: Option<Option<Result<Event<'l>>>>,
+    lenient: bool,
+    spacing: bool,
+    case_insensitive: bool,
+    renamed: Vec<(usize, &'l str)>,
+    start: ((usize, usize), usize),
+    verbatim: bool,
+    opaque: Option<&'l str>,
+    max_attributes: Option<usize>,
+}
+
+/// A parser yielding events together with the ranges of the content they
+/// were read from, which is produced by `Parser::with_spans`.
+pub struct Spans<'l> {
+    parser: Parser<'l>,
+    offset: usize,
 }
 
 /// A result.
@@ -21,7 +40,9 @@ pub type Result<T> = ::std::result::Result<T, Error>;
 
 macro_rules! raise(
     ($parser:expr, $($argument:tt)*) => (
-        return Some(Err(Error::new($parser.reader.position(), format!($($argument)*))));
+        return Some(Err(
+            Error::new($parser.start.0, format!($($argument)*)).with_offset($parser.start.1)
+        ));
     );
 );
 
@@ -31,10 +52,146 @@ impl<'l> Parser<'l> {
     pub fn new(content: &'l str) -> Self {
         Parser {
             reader: Reader::new(content),
+            peeked: None,
+            lenient: false,
+            spacing: false,
+            case_insensitive: false,
+            renamed: Vec::new(),
+            start: ((1, 1), 0),
+            verbatim: false,
+            opaque: None,
+            max_attributes: None,
+        }
+    }
+
+    /// Accept markup that browsers tolerate but XML forbids, such as
+    /// attribute values without quotes.
+    #[inline]
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Record the whitespace around attributes so that tags can be written
+    /// back as they appear in the content; see `Value::spacing`.
+    ///
+    /// The whitespace preceding the end of a tag is kept only if the tag has
+    /// attributes. Attributes are written in the order they appear in the
+    /// content rather than in the order of their names.
+    #[inline]
+    pub fn preserve_spacing(mut self) -> Self {
+        self.spacing = true;
+        self
+    }
+
+    /// Fail with an error on a tag with more than the given number of
+    /// attributes, which is detected before the attributes are collected.
+    #[inline]
+    pub fn max_attributes(mut self, attributes: usize) -> Self {
+        self.max_attributes = Some(attributes);
+        self
+    }
+
+    /// Report the content of `<metadata>` elements as a single raw text
+    /// event instead of interpreting it, so that foreign markup such as RDF
+    /// is written back byte for byte.
+    #[inline]
+    pub fn opaque_metadata(mut self) -> Self {
+        self.verbatim = true;
+        self
+    }
+
+    /// Match the names of known elements regardless of case, which some
+    /// malformed documents rely on.
+    ///
+    /// A name such as `PATH` or `ClipPath` is reported as `path` or
+    /// `clipPath`, respectively, so it can be compared with the constants in
+    /// `tag`. Other names are left as they are. The name of every renamed
+    /// tag as it appears in the content is available via `renamed_tags`.
+    #[inline]
+    pub fn case_insensitive_tags(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Return the names found in the content for the tags reported under a
+    /// different name, each paired with the byte offset of its tag, which
+    /// allows for restoring the original spelling of every occurrence.
+    #[inline]
+    pub fn renamed_tags(&self) -> &[(usize, &'l str)] {
+        &self.renamed
+    }
+
+    /// Yield the events together with the ranges of bytes of the content they
+    /// were read from.
+    ///
+    /// The ranges are contiguous and cover the whole content, so whitespace
+    /// that is not reported as text is included in the range of the event
+    /// following it or, at the end, preceding it. An event peeked beforehand
+    /// is given an empty range.
+    #[inline]
+    pub fn with_spans(self) -> Spans<'l> {
+        Spans {
+            offset: self.reader.byte_offset(),
+            parser: self,
         }
     }
 
+    /// Feed the remaining events to a handler, stopping at the first error.
+    pub fn drive<H>(&mut self, handler: &mut H) -> Result<()>
+    where
+        H: Handler<'l>,
+    {
+        for event in self {
+            match event? {
+                Event::Tag(name, kind, attributes) => {
+                    if let Type::Start | Type::Empty = kind {
+                        handler.start_element(name, &attributes);
+                    }
+                    if let Type::End | Type::Empty = kind {
+                        handler.end_element(name);
+                    }
+                }
+                Event::Text(content) | Event::RawText(content) => handler.text(content),
+                Event::Comment(content) => handler.comment(content, true),
+                Event::UnpaddedComment(content) => handler.comment(content, false),
+                Event::Declaration(content) => handler.declaration(content),
+                Event::Instruction(content) => handler.instruction(content),
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the next event without consuming it.
+    pub fn peek(&mut self) -> Option<&Result<Event<'l>>> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.read_next());
+        }
+        self.peeked.as_ref().and_then(Option::as_ref)
+    }
+
+    fn read_next(&mut self) -> Option<Result<Event<'l>>> {
+        if let Some(name) = self.opaque.take() {
+            let end = format!("</{}", name);
+            let case_insensitive = self.case_insensitive;
+            if let Some(content) = self.reader.capture_raw(|reader| match case_insensitive {
+                true => reader.consume_until_str_ignore_case(&end),
+                false => reader.consume_until_str(&end),
+            }) {
+                return Some(Ok(Event::RawText(content)));
+            }
+        }
+        let event = self.next_text().or_else(|| self.next_angle());
+        if let Some(Ok(Event::Tag(name, Type::Start, _))) = event {
+            if self.verbatim && OPAQUE.contains(&name) {
+                self.opaque = Some(name);
+            }
+        }
+        event
+    }
+
     fn next_angle(&mut self) -> Option<Result<Event<'l>>> {
+        self.start = (self.reader.position(), self.reader.byte_offset());
         let content: String = self.reader.peek_many().take(4).collect();
         if content.is_empty() {
             None
@@ -92,10 +249,39 @@ impl<'l> Parser<'l> {
     fn read_tag(&mut self) -> Option<Result<Event<'l>>> {
         match self.reader.capture(|reader| reader.consume_tag()) {
             None => raise!(self, "found a malformed tag"),
-            Some(content) => Some(
-                Tag::parse(&content[1..content.len() - 1])
-                    .map(|Tag(name, kind, attributes)| Event::Tag(name, kind, attributes)),
-            ),
+            Some(content) => {
+                let ((line, column), offset) = self.start;
+                Some(
+                    Tag::parse_with(
+                        &content[1..content.len() - 1],
+                        self.lenient,
+                        self.spacing,
+                        self.max_attributes,
+                    )
+                    .map_err(|error| error.within((line, column + 1), offset + 1))
+                    .map(|Tag(name, kind, attributes)| {
+                        Event::Tag(self.canonical_name(name), kind, attributes)
+                    }),
+                )
+            }
+        }
+    }
+}
+
+impl<'l> Parser<'l> {
+    fn canonical_name(&mut self, name: &'l str) -> &'l str {
+        if !self.case_insensitive || tag::NAMES.contains(&name) {
+            return name;
+        }
+        match tag::NAMES
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(name))
+        {
+            Some(&known) => {
+                self.renamed.push((self.start.1, name));
+                known
+            }
+            _ => name,
         }
     }
 }
@@ -104,14 +290,37 @@ impl<'l> Iterator for Parser<'l> {
     type Item = Result<Event<'l>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next_text().or_else(|| self.next_angle())
+        match self.peeked.take() {
+            Some(event) => event,
+            None => self.read_next(),
+        }
+    }
+}
+
+impl<'l> Iterator for Spans<'l> {
+    type Item = Result<(Event<'l>, Range<usize>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.parser.next()?;
+        let reader = &self.parser.reader;
+        let end = if reader.peek_many().all(char::is_whitespace) {
+            reader.byte_offset() + reader.peek_many().as_str().len()
+        } else {
+            reader.byte_offset()
+        };
+        let span = self.offset..end;
+        self.offset = end;
+        Some(event.map(|event| (event, span)))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Parser;
+    use super::{Handler, Parser};
     use crate::events::Event;
+    use crate::node::element::tag::{self, Type};
+    use crate::node::Attributes;
+    use crate::Composer;
 
     #[test]
     fn next_tag() {
@@ -130,6 +339,227 @@ mod tests {
         test!("  <foo/>", "foo");
     }
 
+    #[test]
+    fn drive() {
+        #[derive(Default)]
+        struct Names(Vec<String>);
+
+        impl<'l> Handler<'l> for Names {
+            fn start_element(&mut self, name: &'l str, _: &Attributes) {
+                self.0.push(name.into());
+            }
+
+            fn end_element(&mut self, name: &'l str) {
+                self.0.push(format!("/{}", name));
+            }
+
+            fn comment(&mut self, content: &'l str, _: bool) {
+                self.0.push(format!("!{}", content));
+            }
+        }
+
+        let mut names = Names::default();
+        Parser::new("<svg><g><rect/>foo</g><!-- bar --><circle/></svg>")
+            .drive(&mut names)
+            .unwrap();
+        assert_eq!(
+            names.0,
+            ["svg", "g", "rect", "/rect", "/g", "!bar", "circle", "/circle", "/svg"],
+        );
+
+        assert!(Parser::new("<svg><</svg>").drive(&mut names).is_err());
+    }
+
+    #[test]
+    fn borrow_content() {
+        let content = "<!-- foo --><a>bar baz</a><!bar><?baz?>";
+        let borrowed = |value: &str| content.as_bytes().as_ptr_range().contains(&value.as_ptr());
+        let mut count = 0;
+        for event in Parser::new(content) {
+            match event.unwrap() {
+                Event::Tag(value, _, _)
+                | Event::Text(value)
+                | Event::RawText(value)
+                | Event::Comment(value)
+                | Event::UnpaddedComment(value)
+                | Event::Declaration(value)
+                | Event::Instruction(value) => assert!(borrowed(value)),
+            }
+            count += 1;
+        }
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn with_spans() {
+        let content = "<svg>\n  <rect/>\n  text\n</svg>\n";
+        let spans = Parser::new(content)
+            .with_spans()
+            .map(|result| result.unwrap().1)
+            .collect::<Vec<_>>();
+
+        assert_eq!(&content[spans[0].clone()], "<svg>");
+        assert_eq!(&content[spans[1].clone()], "\n  <rect/>");
+        assert_eq!(&content[spans[2].clone()], "\n  text\n");
+        assert_eq!(&content[spans[3].clone()], "</svg>\n");
+        assert_eq!(spans.len(), 4);
+        for pair in spans.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+    }
+
+    #[test]
+    fn case_insensitive_tags() {
+        let mut parser =
+            Parser::new("<SVG><PATH/><ClipPath></ClipPath><Foo/></SVG>").case_insensitive_tags();
+        let names = parser
+            .by_ref()
+            .map(|event| match event.unwrap() {
+                Event::Tag(name, _, _) => name,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            [
+                tag::SVG,
+                tag::Path,
+                tag::ClipPath,
+                tag::ClipPath,
+                "Foo",
+                tag::SVG
+            ],
+        );
+        assert_eq!(
+            parser.renamed_tags(),
+            [
+                (0, "SVG"),
+                (5, "PATH"),
+                (12, "ClipPath"),
+                (22, "ClipPath"),
+                (39, "SVG")
+            ],
+        );
+
+        match Parser::new("<SVG/>").next().unwrap().unwrap() {
+            Event::Tag(name, _, _) => assert_eq!(name, "SVG"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn case_insensitive_opaque_metadata() {
+        let parser = Parser::new("<SVG><METADATA><x>&</x></METADATA><rect/></SVG>")
+            .case_insensitive_tags()
+            .opaque_metadata();
+        let events = parser.map(Result::unwrap).collect::<Vec<_>>();
+
+        assert!(matches!(
+            &events[..],
+            [
+                Event::Tag(tag::SVG, Type::Start, _),
+                Event::Tag(tag::Metadata, Type::Start, _),
+                Event::RawText("<x>&</x>"),
+                Event::Tag(tag::Metadata, Type::End, _),
+                Event::Tag(tag::Rectangle, Type::Empty, _),
+                Event::Tag(tag::SVG, Type::End, _),
+            ]
+        ));
+    }
+
+    #[test]
+    fn peek() {
+        let mut parser = Parser::new("<foo><bar/></foo>");
+        match parser.peek().unwrap().as_ref().unwrap() {
+            Event::Tag(value, _, _) => assert_eq!(*value, "foo"),
+            _ => unreachable!(),
+        }
+        match parser.next().unwrap().unwrap() {
+            Event::Tag(value, _, _) => assert_eq!(value, "foo"),
+            _ => unreachable!(),
+        }
+        match parser.next().unwrap().unwrap() {
+            Event::Tag(value, _, _) => assert_eq!(value, "bar"),
+            _ => unreachable!(),
+        }
+        assert!(parser.next().is_some());
+        assert!(parser.peek().is_none());
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    fn preserve_spacing() {
+        fn compose(parser: Parser) -> String {
+            let mut output = Vec::new();
+            let mut composer = Composer::new(&mut output);
+            for event in parser {
+                composer.write_event(&event.unwrap()).unwrap();
+            }
+            String::from_utf8(output).unwrap()
+        }
+
+        let content = r#"<rect  x="1"   y='2' />"#;
+        assert_eq!(compose(Parser::new(content).preserve_spacing()), content);
+        assert_eq!(compose(Parser::new(content)), r#"<rect x="1" y='2'/>"#);
+
+        let content = "<g\n\tclass=\"a\"\n\tid=\"b\"\n>";
+        assert_eq!(compose(Parser::new(content).preserve_spacing()), content);
+
+        let content = r#"<rect b="1"  a="2" />"#;
+        assert_eq!(compose(Parser::new(content).preserve_spacing()), content);
+        assert_eq!(compose(Parser::new(content)), r#"<rect a="2" b="1"/>"#);
+    }
+
+    #[test]
+    fn lenient() {
+        let mut parser = Parser::new("<rect width=10 height=20/>").lenient();
+        match parser.next().unwrap().unwrap() {
+            Event::Tag("rect", Type::Empty, attributes) => {
+                assert_eq!(attributes.len(), 2);
+                assert_eq!(&*attributes["width"], "10");
+                assert_eq!(&*attributes["height"], "20");
+            }
+            _ => unreachable!(),
+        }
+
+        assert!(Parser::new("<rect width=10/>").next().unwrap().is_err());
+
+        let content = "<svg>\n  </g/>";
+        let mut parser = Parser::new(content).lenient();
+        assert!(parser.next().unwrap().is_ok());
+        let error = parser.next().unwrap().unwrap_err();
+
+        assert_eq!(error.offset(), Some(11));
+        assert_eq!(&content[error.offset().unwrap()..], "/>");
+        assert_eq!((error.line(), error.column()), (2, 6));
+    }
+
+    #[test]
+    fn error_offset() {
+        let content = "<a>é\n</a><b";
+        let mut parser = Parser::new(content);
+        for _ in 0..3 {
+            assert!(parser.next().unwrap().is_ok());
+        }
+        let error = parser.next().unwrap().unwrap_err();
+
+        assert_eq!(error.offset(), Some(10));
+        assert_eq!(&content[error.offset().unwrap()..], "<b");
+        assert_eq!((error.line(), error.column()), (2, 5));
+
+        let content = "<svg>\n  <é/>\n  <rect width=10/>\n</svg>";
+        let mut parser = Parser::new(content);
+        for _ in 0..2 {
+            assert!(parser.next().unwrap().is_ok());
+        }
+        let error = parser.next().unwrap().unwrap_err();
+
+        assert_eq!(error.offset(), Some(31));
+        assert_eq!(&content[error.offset().unwrap()..], ">\n</svg>");
+        assert_eq!((error.line(), error.column()), (3, 18));
+    }
+
     #[test]
     fn next_text() {
         macro_rules! test(
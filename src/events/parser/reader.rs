@@ -5,6 +5,7 @@ pub struct Reader<'l> {
     line: usize,
     column: usize,
     offset: usize,
+    characters: usize,
     content: &'l str,
     cursor: Peekable<Chars<'l>>,
 }
@@ -16,6 +17,7 @@ impl<'l> Reader<'l> {
             line: 1,
             column: 1,
             offset: 0,
+            characters: 0,
             content,
             cursor: content.chars().peekable(),
         }
@@ -37,6 +39,18 @@ impl<'l> Reader<'l> {
         }
     }
 
+    /// Capture the content consumed by the block without trimming it.
+    pub fn capture_raw<F>(&mut self, block: F) -> Option<&'l str>
+    where
+        F: Fn(&mut Reader<'l>) -> bool,
+    {
+        let start = self.offset;
+        if !block(self) {
+            return None;
+        }
+        Some(&self.content[start..self.offset])
+    }
+
     #[inline]
     pub fn consume_all(&mut self) -> bool {
         self.consume_while(|_| true)
@@ -76,6 +90,11 @@ impl<'l> Reader<'l> {
         self.consume_char(if single { '\'' } else { '"' })
     }
 
+    // https://html.spec.whatwg.org/#unquoted
+    pub fn consume_unquoted_attribute_value(&mut self) -> bool {
+        self.consume_while(|c| !"\x20\x09\x0D\x0A\"'=<>`".contains(c))
+    }
+
     pub fn consume_char(&mut self, target: char) -> bool {
         match self.peek() {
             Some(c) if c == target => {
@@ -248,6 +267,28 @@ impl<'l> Reader<'l> {
         self.consume_while(|c| c != target)
     }
 
+    pub fn consume_until_str(&mut self, target: &str) -> bool {
+        let mut consumed = false;
+        while !self.is_done() && !self.content[self.offset..].starts_with(target) {
+            self.next();
+            consumed = true;
+        }
+        consumed
+    }
+
+    pub fn consume_until_str_ignore_case(&mut self, target: &str) -> bool {
+        let mut consumed = false;
+        while !self.is_done()
+            && !self.content[self.offset..]
+                .get(..target.len())
+                .map_or(false, |prefix| prefix.eq_ignore_ascii_case(target))
+        {
+            self.next();
+            consumed = true;
+        }
+        consumed
+    }
+
     pub fn consume_while<F>(&mut self, check: F) -> bool
     where
         F: Fn(char) -> bool,
@@ -280,11 +321,26 @@ impl<'l> Reader<'l> {
         self.content[self.offset..].chars()
     }
 
+    /// Return the line and the column, both starting at one, with the column
+    /// counted in characters.
     #[inline]
     pub fn position(&self) -> (usize, usize) {
         (self.line, self.column)
     }
 
+    /// Return the offset from the start of the content in bytes, which can be
+    /// used for indexing the content.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Return the offset from the start of the content in characters.
+    #[inline]
+    pub fn char_offset(&self) -> usize {
+        self.characters
+    }
+
     // https://www.w3.org/TR/REC-xml/#NT-Char
     fn check_character(target: char) -> bool {
         match target {
@@ -351,6 +407,7 @@ impl<'l> Iterator for Reader<'l> {
                     self.column += 1;
                 }
                 self.offset += c.len_utf8();
+                self.characters += 1;
                 Some(c)
             }
             _ => None,
@@ -373,6 +430,16 @@ mod tests {
         assert_eq!(content.unwrap(), "cde");
     }
 
+    #[test]
+    fn offsets() {
+        let mut reader = Reader::new("aé\nb");
+        reader.consume_until_char('b');
+
+        assert_eq!(reader.byte_offset(), 4);
+        assert_eq!(reader.char_offset(), 3);
+        assert_eq!(reader.position(), (2, 1));
+    }
+
     #[test]
     fn consume_attribute() {
         macro_rules! test(
@@ -68,6 +68,11 @@ use std::path::Path;
 
 pub mod events;
 pub mod node;
+pub mod scan;
+
+#[cfg(feature = "encoding")]
+mod encoding;
+pub mod xml;
 
 pub use crate::events::composer::Composer;
 pub use crate::events::parser::Parser;
@@ -90,6 +95,17 @@ pub fn read<'l>(content: &'l str) -> io::Result<Parser<'l>> {
     Ok(Parser::new(content))
 }
 
+/// Read a document given as bytes in an encoding other than UTF-8.
+///
+/// The encoding is detected using the byte order mark or, in its absence, the
+/// XML declaration, and the document is decoded into `content`. UTF-8, UTF-16,
+/// US-ASCII, ISO-8859-1, and Windows-1252 are supported.
+#[cfg(feature = "encoding")]
+pub fn read_bytes<'l>(bytes: &[u8], content: &'l mut String) -> io::Result<Parser<'l>> {
+    *content = encoding::decode(bytes)?;
+    read(content)
+}
+
 /// Save a document.
 pub fn save<'l, T, U>(path: T, document: U) -> io::Result<()>
 where
@@ -109,8 +125,8 @@ where
     let document: &Document = document.as_ref();
     let mut composer = Composer::new(&mut target);
     document
-        .to_events()
-        .try_for_each(|event| composer.write_event(&event))
+        .to_compact_events()
+        .try_for_each(|event| composer.write_compact_event(event))
 }
 
 #[cfg(test)]
@@ -138,6 +154,23 @@ mod tests {
         exercise(crate::read(&content).unwrap());
     }
 
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn read_bytes() {
+        let mut bytes = b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>\n<text>".to_vec();
+        bytes.extend_from_slice(&[b'N', 0xE4, b'h', 0xE9]);
+        bytes.extend_from_slice(b"</text>");
+        let mut content = String::new();
+        let mut parser = crate::read_bytes(&bytes, &mut content).unwrap();
+
+        assert!(matches!(parser.next(), Some(Ok(Event::Instruction(_)))));
+        assert!(matches!(parser.next(), Some(Ok(Event::Tag("text", _, _)))));
+        assert!(matches!(
+            parser.next(),
+            Some(Ok(Event::Text("N\u{E4}h\u{E9}")))
+        ));
+    }
+
     fn exercise<'l>(mut parser: Parser<'l>) {
         macro_rules! test(
             ($matcher:pat) => (match parser.next().unwrap() {
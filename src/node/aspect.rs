@@ -0,0 +1,191 @@
+//! The aspect ratio preservation.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::events::parser::{Error, Reader, Result};
+use crate::node::Value;
+
+/// A [`preserveAspectRatio`](https://www.w3.org/TR/SVG/coords.html#PreserveAspectRatioAttribute)
+/// attribute.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PreserveAspectRatio {
+    /// The alignment.
+    pub align: Align,
+    /// The scaling.
+    pub meet_or_slice: MeetOrSlice,
+}
+
+/// An alignment of a `viewBox` within a viewport.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Align {
+    /// `none`, that is, nonuniform scaling.
+    None,
+    /// `xMinYMin`.
+    XMinYMin,
+    /// `xMidYMin`.
+    XMidYMin,
+    /// `xMaxYMin`.
+    XMaxYMin,
+    /// `xMinYMid`.
+    XMinYMid,
+    /// `xMidYMid`.
+    #[default]
+    XMidYMid,
+    /// `xMaxYMid`.
+    XMaxYMid,
+    /// `xMinYMax`.
+    XMinYMax,
+    /// `xMidYMax`.
+    XMidYMax,
+    /// `xMaxYMax`.
+    XMaxYMax,
+}
+
+/// A scaling of a `viewBox` within a viewport.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MeetOrSlice {
+    /// `meet`, that is, the `viewBox` is entirely visible.
+    #[default]
+    Meet,
+    /// `slice`, that is, the viewport is entirely covered.
+    Slice,
+}
+
+const ALIGNS: &[(&str, Align)] = &[
+    ("none", Align::None),
+    ("xMinYMin", Align::XMinYMin),
+    ("xMidYMin", Align::XMidYMin),
+    ("xMaxYMin", Align::XMaxYMin),
+    ("xMinYMid", Align::XMinYMid),
+    ("xMidYMid", Align::XMidYMid),
+    ("xMaxYMid", Align::XMaxYMid),
+    ("xMinYMax", Align::XMinYMax),
+    ("xMidYMax", Align::XMidYMax),
+    ("xMaxYMax", Align::XMaxYMax),
+];
+
+impl PreserveAspectRatio {
+    /// Create an attribute.
+    #[inline]
+    pub fn new(align: Align, meet_or_slice: MeetOrSlice) -> Self {
+        PreserveAspectRatio {
+            align,
+            meet_or_slice,
+        }
+    }
+
+    /// Parse an attribute.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::new(content);
+        let mut next = || {
+            reader.consume_while(char::is_whitespace);
+            let position = reader.position();
+            let word = reader.capture(|reader| reader.consume_while(|c| !c.is_whitespace()));
+            (position, word)
+        };
+        let align = match next() {
+            (position, Some(word)) => match ALIGNS.iter().find(|(name, _)| *name == word) {
+                Some((_, align)) => *align,
+                _ => {
+                    let message = format!("found an unknown alignment '{}'", word);
+                    return Err(Error::new(position, message));
+                }
+            },
+            (position, _) => return Err(Error::new(position, "expected an alignment")),
+        };
+        let meet_or_slice = match next() {
+            (_, None) | (_, Some("meet")) => MeetOrSlice::Meet,
+            (_, Some("slice")) => MeetOrSlice::Slice,
+            (position, Some(word)) => {
+                let message = format!("found an unknown scaling '{}'", word);
+                return Err(Error::new(position, message));
+            }
+        };
+        if let (position, Some(_)) = next() {
+            return Err(Error::new(position, "found excessive data"));
+        }
+        Ok(PreserveAspectRatio::new(align, meet_or_slice))
+    }
+}
+
+impl Align {
+    /// Return the name of the alignment.
+    pub fn as_str(self) -> &'static str {
+        ALIGNS.iter().find(|(_, align)| *align == self).unwrap().0
+    }
+
+    /// Return the fractions of the free space placed before the `viewBox`
+    /// horizontally and vertically, or `None` for nonuniform scaling.
+    pub(crate) fn factors(self) -> Option<(f64, f64)> {
+        let name = self.as_str();
+        let factor = |name: &str| match name {
+            "Min" => 0.0,
+            "Mid" => 0.5,
+            _ => 1.0,
+        };
+        match self {
+            Align::None => None,
+            _ => Some((factor(&name[1..4]), factor(&name[5..8]))),
+        }
+    }
+}
+
+impl fmt::Display for PreserveAspectRatio {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.align.as_str().fmt(formatter)?;
+        match self.meet_or_slice {
+            MeetOrSlice::Meet => Ok(()),
+            MeetOrSlice::Slice => " slice".fmt(formatter),
+        }
+    }
+}
+
+impl FromStr for PreserveAspectRatio {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(content: &str) -> Result<Self> {
+        PreserveAspectRatio::parse(content)
+    }
+}
+
+impl From<PreserveAspectRatio> for Value {
+    #[inline]
+    fn from(aspect: PreserveAspectRatio) -> Self {
+        aspect.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Align, MeetOrSlice, PreserveAspectRatio};
+
+    #[test]
+    fn preserve_aspect_ratio_parse() {
+        let aspect = "xMinYMax slice".parse::<PreserveAspectRatio>().unwrap();
+        assert_eq!(
+            aspect,
+            PreserveAspectRatio::new(Align::XMinYMax, MeetOrSlice::Slice),
+        );
+        assert_eq!(aspect.to_string(), "xMinYMax slice");
+
+        let aspect = "none".parse::<PreserveAspectRatio>().unwrap();
+        assert_eq!(aspect.align, Align::None);
+        assert_eq!(aspect.to_string(), "none");
+
+        assert_eq!(
+            "xMidYMid meet".parse::<PreserveAspectRatio>().unwrap(),
+            PreserveAspectRatio::default(),
+        );
+        assert!("xMinYMax crop".parse::<PreserveAspectRatio>().is_err());
+        assert!("center".parse::<PreserveAspectRatio>().is_err());
+
+        let error = PreserveAspectRatio::parse(" xMinYMax  crop").unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 12));
+        let error = PreserveAspectRatio::parse("none meet x").unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 11));
+        let error = PreserveAspectRatio::parse("  ").unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 3));
+    }
+}
@@ -0,0 +1,432 @@
+//! The cleanup passes.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use crate::node::element::{tag, GenericElement};
+use crate::node::lint::{collect_references, references};
+use crate::node::{Document, Node, Value};
+
+/// Elements removed by `Document::strip_editor_metadata`.
+pub const EDITOR_ELEMENTS: &[&str] = &["metadata"];
+
+/// Namespace prefixes removed by `Document::strip_editor_metadata`.
+pub const EDITOR_NAMESPACES: &[&str] = &["inkscape", "sodipodi"];
+
+/// Elements that are only rendered when referenced, which
+/// `Document::remove_unused_defs` considers outside of `defs` too.
+pub const DEFINITIONS: &[&str] = &[
+    tag::ClipPath,
+    tag::Filter,
+    tag::LinearGradient,
+    tag::Marker,
+    tag::Mask,
+    tag::Pattern,
+    tag::RadialGradient,
+    tag::Symbol,
+];
+
+impl<'l> Document<'l> {
+    /// Remove metadata left by editors such as Inkscape and Illustrator.
+    ///
+    /// It is the same as `strip_metadata` with `EDITOR_ELEMENTS` and
+    /// `EDITOR_NAMESPACES`.
+    #[inline]
+    pub fn strip_editor_metadata(&mut self) {
+        self.strip_metadata(EDITOR_ELEMENTS, EDITOR_NAMESPACES);
+    }
+
+    /// Remove the given elements as well as elements and attributes in the
+    /// given namespaces, identified by their prefixes, recursively.
+    ///
+    /// The declarations of the namespaces (`xmlns:prefix`) are removed too.
+    pub fn strip_metadata(&mut self, elements: &[&str], namespaces: &[&str]) {
+        strip(self.get_mut_svg(), elements, namespaces);
+    }
+
+    /// Remove the definitions that are not referred to.
+    ///
+    /// Definitions are the children of `defs` elements as well as the children
+    /// of the root element listed in `DEFINITIONS`, provided that they have an
+    /// `id`. A definition is kept if it is referred to, in the sense of
+    /// `referenced_ids`, by an element that is not a definition or by a kept
+    /// definition or any of its descendants. References from style sheets in
+    /// `style` elements count, whereas those from external style sheets are
+    /// unknown.
+    pub fn remove_unused_defs(&mut self) {
+        let svg = self.get_svg();
+        let mut used = HashSet::new();
+        used.extend(references(svg).into_iter().map(String::from));
+        collect_rendered_references(svg, true, &mut used);
+        let mut elements = HashMap::new();
+        index_ids(svg, &mut elements);
+        let mut pending = used.iter().cloned().collect::<Vec<_>>();
+        while let Some(id) = pending.pop() {
+            let mut found = HashSet::new();
+            if let Some(element) = elements.get(&*id) {
+                collect_references(element, &mut found);
+            }
+            for id in found {
+                if used.insert(id.clone()) {
+                    pending.push(id);
+                }
+            }
+        }
+        remove_unused(self.get_mut_svg(), true, &used);
+    }
+
+    /// Round the numbers in the values of attributes to the given number of
+    /// decimal places, recursively.
+    ///
+    /// Numbers are recognized anywhere in values, including lists such as
+    /// `points`, functions such as those in `transform`, and lengths with
+    /// units, as well as path data in `d`. Numbers that are part of words, as
+    /// in `#a1b2c3`, are left intact, as are the `id`, `class`, `href`, and
+    /// `xlink:href` attributes, raw values, numbers with no more decimal
+    /// places than requested, and numbers too large to be rounded.
+    pub fn round_coordinates(&mut self, decimals: usize) {
+        round_coordinates(self.get_mut_svg(), decimals);
+    }
+
+    /// Collapse the root element as long as its only child is another `<svg>`
+    /// element, which is what creating a document from a root element yields.
+    ///
+    /// Blank text around the inner element is dropped. The attributes of the
+    /// inner element take precedence, and those only assigned on the outer
+    /// one, such as namespace declarations, are carried over.
+    pub fn flatten_redundant_svg(&mut self) {
+        let svg = self.get_mut_svg();
+        loop {
+            let mut nodes = svg.get_children().iter().filter(|node| !node.is_blank());
+            match (nodes.next(), nodes.next()) {
+                (Some(Node::Element(inner)), None) if inner.get_name() == tag::SVG => {}
+                _ => return,
+            }
+            let mut inner = svg
+                .get_mut_children()
+                .drain(..)
+                .find_map(|node| match node {
+                    Node::Element(inner) => Some(inner),
+                    _ => None,
+                })
+                .unwrap();
+            let attributes = inner.get_mut_attributes();
+            for (name, value) in mem::take(svg.get_mut_attributes()) {
+                attributes.entry(name).or_insert(value);
+            }
+            *svg = inner;
+        }
+    }
+}
+
+fn is_definition(parent: &str, root: bool, element: &GenericElement) -> bool {
+    element.get_attributes().contains_key("id")
+        && (parent == tag::Definitions || root && DEFINITIONS.contains(&element.get_name()))
+}
+
+fn index_ids<'a>(
+    element: &'a GenericElement<'a>,
+    elements: &mut HashMap<&'a str, &'a GenericElement<'a>>,
+) {
+    if let Some(id) = element.get_attributes().get("id") {
+        elements.entry(&**id).or_insert(element);
+    }
+    for child in element.get_children() {
+        if let Node::Element(child) = child {
+            index_ids(child, elements);
+        }
+    }
+}
+
+fn collect_rendered_references(element: &GenericElement, root: bool, ids: &mut HashSet<String>) {
+    for child in element.get_children() {
+        if let Node::Element(child) = child {
+            if !is_definition(element.get_name(), root, child) {
+                ids.extend(references(child).into_iter().map(String::from));
+                collect_rendered_references(child, false, ids);
+            }
+        }
+    }
+}
+
+fn remove_unused(element: &mut GenericElement, root: bool, used: &HashSet<String>) {
+    let name = element.get_name().to_string();
+    element.get_mut_children().retain(|child| match child {
+        Node::Element(child) if is_definition(&name, root, child) => {
+            used.contains(&*child.get_attributes()["id"])
+        }
+        _ => true,
+    });
+    for child in element.get_mut_children() {
+        if let Node::Element(child) = child {
+            remove_unused(child, false, used);
+        }
+    }
+}
+
+fn round_coordinates(element: &mut GenericElement, decimals: usize) {
+    for (name, value) in element.get_mut_attributes().iter_mut() {
+        if value.is_raw()
+            || value.is_flag()
+            || ["id", "class", "href", "xlink:href"].contains(&name.as_str())
+        {
+            continue;
+        }
+        let rounded = round_numbers(value, decimals, name == "d");
+        if rounded != **value {
+            let mut replacement = Value::from(rounded);
+            if let Some(quote) = value.quote() {
+                replacement = replacement.with_quote(quote);
+            }
+            if let Some((before, after)) = value.spacing() {
+                replacement = replacement.with_source_spacing(before, after, value.order());
+            }
+            *value = replacement;
+        }
+    }
+    for child in element.get_mut_children() {
+        if let Node::Element(child) = child {
+            round_coordinates(child, decimals);
+        }
+    }
+}
+
+// Letters delimit numbers in path data, whereas they make numbers part of
+// words elsewhere.
+fn round_numbers(content: &str, decimals: usize, letters: bool) -> String {
+    let bytes = content.as_bytes();
+    let digit = |i: usize| bytes.get(i).map_or(false, u8::is_ascii_digit);
+    let mut rounded = String::with_capacity(content.len());
+    let mut i = 0;
+    // The end of the last number, after which a period starts another one,
+    // as in `0.5.5` in path data, and whether it was written with a period.
+    let mut end = usize::MAX;
+    let mut point = false;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let starts = match c {
+            b'0'..=b'9' => true,
+            b'.' => digit(i + 1),
+            b'+' | b'-' => digit(i + 1) || bytes.get(i + 1) == Some(&b'.') && digit(i + 2),
+            _ => false,
+        };
+        let word = i > 0 && {
+            let previous = bytes[i - 1];
+            (previous.is_ascii_alphabetic() && !letters)
+                || previous == b'_'
+                || previous == b'#'
+                || (previous.is_ascii_digit() || previous == b'.')
+                    && !matches!(c, b'+' | b'-')
+                    && i != end
+        };
+        if !starts || word {
+            let length = content[i..].chars().next().map_or(1, char::len_utf8);
+            rounded.push_str(&content[i..(i + length)]);
+            i += length;
+            continue;
+        }
+        let start = i;
+        if matches!(c, b'+' | b'-') {
+            i += 1;
+        }
+        while digit(i) {
+            i += 1;
+        }
+        let mut fraction = 0;
+        if bytes.get(i) == Some(&b'.') {
+            i += 1;
+            while digit(i) {
+                i += 1;
+                fraction += 1;
+            }
+        }
+        let mut exponent = false;
+        if matches!(bytes.get(i), Some(b'e' | b'E')) {
+            let sign = matches!(bytes.get(i + 1), Some(b'+' | b'-')) as usize;
+            if digit(i + 1 + sign) {
+                exponent = true;
+                i += 1 + sign;
+                while digit(i) {
+                    i += 1;
+                }
+            }
+        }
+        let number = &content[start..i];
+        let value = match number.parse::<f64>() {
+            Ok(value) if exponent || fraction > decimals => {
+                let scale = 10f64.powi(decimals.min(i32::MAX as usize) as i32);
+                let rounded = (value * scale).round() / scale;
+                match rounded.is_finite() && !(exponent && rounded == value) {
+                    // Avoid writing negative zero.
+                    true => Cow::Owned((rounded + 0.0).to_string()),
+                    false => Cow::Borrowed(number),
+                }
+            }
+            _ => Cow::Borrowed(number),
+        };
+        // Keep adjacent numbers apart if rounding changed their boundary.
+        if letters && start == end {
+            let first = value.as_bytes()[0];
+            if first.is_ascii_digit() || first == b'.' && !point {
+                rounded.push(' ');
+            }
+        }
+        rounded.push_str(&value);
+        end = i;
+        point = value.contains('.');
+    }
+    rounded
+}
+
+fn strip(element: &mut GenericElement, elements: &[&str], namespaces: &[&str]) {
+    let in_namespaces = |name: &str| match name.split_once(':') {
+        Some(("xmlns", prefix)) | Some((prefix, _)) => namespaces.contains(&prefix),
+        None => false,
+    };
+    element
+        .get_mut_attributes()
+        .retain(|name, _| !in_namespaces(name));
+    element.get_mut_children().retain(|child| match child {
+        Node::Element(child) => {
+            let name = child.get_name();
+            !elements.contains(&name) && !in_namespaces(name)
+        }
+        _ => true,
+    });
+    for child in element.get_mut_children() {
+        if let Node::Element(child) = child {
+            strip(child, elements, namespaces);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::element::{Group, Path, SVG};
+    use crate::node::Node;
+    use crate::Document;
+
+    #[test]
+    fn strip_editor_metadata() {
+        let mut document = Document::new()
+            .set(
+                "xmlns:inkscape",
+                "http://www.inkscape.org/namespaces/inkscape",
+            )
+            .set("inkscape:version", "1.0")
+            .add(Node::new_element("metadata"))
+            .add(Node::new_element("sodipodi:namedview"))
+            .add(
+                Group::new()
+                    .set("inkscape:label", "Layer 1")
+                    .set("id", "layer1")
+                    .add(Path::new().set("d", "M0,0")),
+            );
+
+        document.strip_editor_metadata();
+
+        assert_eq!(
+            document.get_svg().to_string(),
+            "<svg>\n<g id=\"layer1\">\n<path d=\"M0,0\"/>\n</g>\n</svg>",
+        );
+    }
+
+    #[test]
+    fn remove_unused_defs() {
+        let mut document = Document::parse(
+            r##"<svg>
+<defs>
+<linearGradient id="base"><stop offset="0"/></linearGradient>
+<linearGradient id="used" href="#base"/>
+<linearGradient id="unused"/>
+<linearGradient id="styled"/>
+<filter id="blur"/>
+<style>rect { stroke: url(#styled); } .soft { filter: url('#blur'); }</style>
+</defs>
+<pattern id="tile"/>
+<clipPath id="clip"/>
+<rect fill="url(#used)" clip-path="url(#clip)"/>
+</svg>"##,
+        )
+        .unwrap();
+        document.remove_unused_defs();
+
+        let svg = document.get_svg();
+        assert!(svg.get_element_by_id("base").is_some());
+        assert!(svg.get_element_by_id("used").is_some());
+        assert!(svg.get_element_by_id("clip").is_some());
+        assert!(svg.get_element_by_id("styled").is_some());
+        assert!(svg.get_element_by_id("blur").is_some());
+        assert!(svg.get_element_by_id("unused").is_none());
+        assert!(svg.get_element_by_id("tile").is_none());
+        assert!(svg.to_string().contains("<style>"));
+    }
+
+    #[test]
+    fn round_coordinates() {
+        let mut document = Document::parse(
+            r##"<svg viewBox="0 0 100.123456 50">
+<g id="a1.2345" fill="#123456" transform="translate(10.555 -0.001) rotate(45)">
+<rect x="10.12345" y="-5.5" width="1e-7" font-size="12.3456px"/>
+<polygon points="1.234,5.678 9.1,2"/>
+<path d="M10.126,20.333L30.5-40.999.5.123z"/>
+</g>
+</svg>"##,
+        )
+        .unwrap();
+        document.round_coordinates(2);
+
+        assert_eq!(
+            document.get_svg().to_string(),
+            r##"<svg viewBox="0 0 100.12 50">
+<g fill="#123456" id="a1.2345" transform="translate(10.56 0) rotate(45)">
+<rect font-size="12.35px" width="0" x="10.12" y="-5.5"/>
+<polygon points="1.23,5.68 9.1,2"/>
+<path d="M10.13,20.33L30.5-41 .5 0.12z"/>
+</g>
+</svg>"##,
+        );
+    }
+
+    #[test]
+    fn round_coordinates_precision() {
+        let mut document =
+            Document::parse(r#"<svg><path d="M1234567.891,0 L1e300,1.23456789"/></svg>"#).unwrap();
+        document.round_coordinates(3);
+        assert_eq!(
+            document.get_svg().to_string(),
+            "<svg>\n<path d=\"M1234567.891,0 L1e300,1.235\"/>\n</svg>",
+        );
+
+        document.round_coordinates(400);
+        assert!(document.get_svg().to_string().contains("1e300"));
+    }
+
+    #[test]
+    fn flatten_redundant_svg() {
+        let mut document = Document::from(
+            SVG::new()
+                .set("xmlns", "http://www.w3.org/2000/svg")
+                .set("width", 10)
+                .add(
+                    SVG::new()
+                        .set("width", 20)
+                        .set("height", 20)
+                        .add(Path::new().set("d", "M0,0")),
+                ),
+        );
+        document.flatten_redundant_svg();
+
+        assert_eq!(
+            document.get_svg().to_string(),
+            "<svg height=\"20\" width=\"20\" xmlns=\"http://www.w3.org/2000/svg\">\n\
+             <path d=\"M0,0\"/>\n\
+             </svg>",
+        );
+
+        let mut document = Document::parse("<svg>\n  <svg><svg/><path/></svg>\n</svg>").unwrap();
+        document.flatten_redundant_svg();
+        assert_eq!(document.get_svg().get_children().len(), 2);
+    }
+}
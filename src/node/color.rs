@@ -0,0 +1,152 @@
+//! The colors.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::events::parser::{Error, Result};
+use crate::node::Value;
+
+/// A [color](https://www.w3.org/TR/css-color-3/).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Color {
+    /// A color given by its red, green, and blue components.
+    Rgb(u8, u8, u8),
+    /// A color given by a keyword, such as `red`, `none`, or `currentColor`.
+    Named(String),
+}
+
+impl Color {
+    /// Parse a color.
+    ///
+    /// The hexadecimal (`#rgb` and `#rrggbb`) and functional (`rgb(r, g, b)`
+    /// with integers or percentages) notations are recognized, and so are
+    /// keywords, which are kept as is. An error points at the beginning of
+    /// the color, as the color is rejected as a whole.
+    pub fn parse(content: &str) -> Result<Self> {
+        let skipped = &content[..(content.len() - content.trim_start().len())];
+        let position = (
+            1 + skipped.matches('\n').count(),
+            1 + skipped.rsplit('\n').next().unwrap_or("").chars().count(),
+        );
+        let malformed = |content| {
+            Err(Error::new(
+                position,
+                format!("found a malformed color '{}'", content),
+            ))
+        };
+        let content = content.trim();
+        if let Some(hex) = content.strip_prefix('#') {
+            let digit = |i: usize| u8::from_str_radix(&hex[i..(i + 1)], 16).ok();
+            let components = match hex.len() {
+                _ if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) => None,
+                3 => (0..3)
+                    .map(|i| digit(i).map(|value| value * 17))
+                    .collect::<Option<Vec<_>>>(),
+                6 => (0..3)
+                    .map(|i| u8::from_str_radix(&hex[(2 * i)..(2 * i + 2)], 16).ok())
+                    .collect::<Option<Vec<_>>>(),
+                _ => None,
+            };
+            return match components {
+                Some(components) => Ok(Color::Rgb(components[0], components[1], components[2])),
+                _ => malformed(content),
+            };
+        }
+        if let Some(arguments) = content
+            .strip_prefix("rgb(")
+            .and_then(|content| content.strip_suffix(')'))
+        {
+            let components = arguments
+                .split(',')
+                .map(|component| {
+                    let component = component.trim();
+                    match component.strip_suffix('%') {
+                        Some(percentage) => percentage
+                            .parse::<f64>()
+                            .ok()
+                            .map(|value| (value.clamp(0.0, 100.0) * 2.55).round() as u8),
+                        _ => component
+                            .parse::<i64>()
+                            .ok()
+                            .map(|value| value.clamp(0, 255) as u8),
+                    }
+                })
+                .collect::<Option<Vec<_>>>();
+            return match components {
+                Some(components) if components.len() == 3 => {
+                    Ok(Color::Rgb(components[0], components[1], components[2]))
+                }
+                _ => malformed(content),
+            };
+        }
+        if !content.is_empty() && content.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Ok(Color::Named(content.into()));
+        }
+        malformed(content)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Rgb(red, green, blue) => {
+                write!(formatter, "#{:02x}{:02x}{:02x}", red, green, blue)
+            }
+            Color::Named(name) => name.fmt(formatter),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(content: &str) -> Result<Self> {
+        Color::parse(content)
+    }
+}
+
+impl From<Color> for Value {
+    #[inline]
+    fn from(color: Color) -> Self {
+        color.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Color;
+
+    #[test]
+    fn color_parse() {
+        assert_eq!(
+            "#ABC".parse::<Color>().unwrap(),
+            Color::Rgb(0xaa, 0xbb, 0xcc)
+        );
+        assert_eq!(
+            "#0080fF".parse::<Color>().unwrap(),
+            Color::Rgb(0, 0x80, 0xff)
+        );
+        assert_eq!(
+            "rgb(255, 0, 100%)".parse::<Color>().unwrap(),
+            Color::Rgb(255, 0, 255),
+        );
+        assert_eq!(
+            "currentColor".parse::<Color>().unwrap(),
+            Color::Named("currentColor".into()),
+        );
+        assert_eq!(Color::Rgb(0xaa, 0xbb, 0xcc).to_string(), "#aabbcc");
+
+        assert!("#abcd".parse::<Color>().is_err());
+        assert!(Color::parse("#+1+2+3").is_err());
+        assert!(Color::parse("#+f+").is_err());
+        assert!("rgb(1, 2)".parse::<Color>().is_err());
+        assert!("url(#a)".parse::<Color>().is_err());
+
+        let error = Color::parse("\n  #ab").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "found a malformed color '#ab' (line 2, column 3)"
+        );
+    }
+}
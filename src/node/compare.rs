@@ -0,0 +1,80 @@
+//! The structural comparison.
+
+use crate::node::element::GenericElement;
+use crate::node::{Document, Node};
+
+impl<'l> Document<'l> {
+    /// Check if the root elements of two documents are the same up to
+    /// formatting.
+    ///
+    /// Elements are compared by their names, attributes, and children in
+    /// order. The order of attributes and text consisting only of whitespace,
+    /// such as indentation, are ignored, and so are the prologs and the nodes
+    /// following the root elements. Other text and comments have to be equal.
+    pub fn semantically_equal(&self, other: &Document) -> bool {
+        equal(self.get_svg(), other.get_svg())
+    }
+}
+
+fn equal(one: &GenericElement, other: &GenericElement) -> bool {
+    if one.get_name() != other.get_name() || one.get_attributes() != other.get_attributes() {
+        return false;
+    }
+    let mut one = one.get_children().iter().filter(|node| !node.is_blank());
+    let mut other = other.get_children().iter().filter(|node| !node.is_blank());
+    loop {
+        match (one.next(), other.next()) {
+            (None, None) => return true,
+            (Some(one), Some(other)) if node_equal(one, other) => {}
+            _ => return false,
+        }
+    }
+}
+
+fn node_equal(one: &Node, other: &Node) -> bool {
+    match (one, other) {
+        (Node::Element(one), Node::Element(other)) => equal(one, other),
+        (Node::Text(one), Node::Text(other))
+        | (Node::RawText(one), Node::RawText(other))
+        | (Node::Comment(one), Node::Comment(other))
+        | (Node::UnpaddedComment(one), Node::UnpaddedComment(other))
+        | (Node::Declaration(one), Node::Declaration(other))
+        | (Node::Instruction(one), Node::Instruction(other)) => one == other,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Document;
+
+    #[test]
+    fn semantically_equal() {
+        let one = Document::parse(
+            r#"<svg width="10" height="10"><g id="a" fill="red"><rect x="1" y="2"/></g></svg>"#,
+        )
+        .unwrap();
+        let other = Document::parse(
+            r#"<svg height="10" width="10">
+  <g fill="red" id="a">
+    <rect y="2" x="1"/>
+  </g>
+</svg>"#,
+        )
+        .unwrap();
+        assert!(one.semantically_equal(&other));
+        assert!(other.semantically_equal(&one));
+
+        let other = Document::parse(
+            r#"<svg height="10" width="10"><g fill="blue" id="a"><rect y="2" x="1"/></g></svg>"#,
+        )
+        .unwrap();
+        assert!(!one.semantically_equal(&other));
+
+        let other = Document::parse(
+            r#"<svg height="10" width="10"><g fill="red" id="a"/><rect y="2" x="1"/></svg>"#,
+        )
+        .unwrap();
+        assert!(!one.semantically_equal(&other));
+    }
+}
@@ -0,0 +1,99 @@
+//! The document type declarations.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::node::Node;
+
+/// A [document type declaration](https://www.w3.org/TR/REC-xml/#dt-doctype)
+/// without an internal subset.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Doctype {
+    name: String,
+    public: Option<String>,
+    system: Option<String>,
+}
+
+impl Doctype {
+    /// Create a declaration without external identifiers.
+    #[inline]
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        Doctype {
+            name: name.into(),
+            public: None,
+            system: None,
+        }
+    }
+
+    /// Create the declaration of [SVG 1.1](https://www.w3.org/TR/SVG11/).
+    #[inline]
+    pub fn svg_1_1() -> Self {
+        Doctype::new("svg").public(
+            "-//W3C//DTD SVG 1.1//EN",
+            "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd",
+        )
+    }
+
+    /// Set a public identifier along with a system one.
+    pub fn public<T, U>(mut self, public: T, system: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.public = Some(public.into());
+        self.system = Some(system.into());
+        self
+    }
+
+    /// Set a system identifier alone.
+    pub fn system<T: Into<String>>(mut self, system: T) -> Self {
+        self.public = None;
+        self.system = Some(system.into());
+        self
+    }
+}
+
+impl fmt::Display for Doctype {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "DOCTYPE {}", self.name)?;
+        match (&self.public, &self.system) {
+            (Some(public), Some(system)) => {
+                write!(formatter, " PUBLIC \"{}\" \"{}\"", public, system)
+            }
+            (_, Some(system)) => write!(formatter, " SYSTEM \"{}\"", system),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'l> From<Doctype> for Node<'l> {
+    #[inline]
+    fn from(doctype: Doctype) -> Self {
+        Node::Declaration(Cow::Owned(doctype.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Doctype;
+    use crate::node::Node;
+    use crate::Composer;
+
+    #[test]
+    fn doctype_svg_1_1() {
+        let mut composed = Vec::new();
+        let mut composer = Composer::new(&mut composed);
+        for event in Node::from(Doctype::svg_1_1()).to_compact_events() {
+            composer.write_compact_event(event).unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(composed).unwrap(),
+            r#"<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">"#,
+        );
+        assert_eq!(
+            Doctype::new("svg").system("svg.dtd").to_string(),
+            r#"DOCTYPE svg SYSTEM "svg.dtd""#,
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! The memoized serialization.
+
+use std::cell::RefCell;
+use std::fmt;
+
+use crate::node::element::GenericElement;
+
+/// An element whose serialization is computed once and reused until the
+/// element is modified.
+///
+/// The element can only be modified via `get_mut`, which discards the
+/// serialization, so the output is always the same as that of the element
+/// itself.
+#[derive(Clone, Debug)]
+pub struct CachedElement<'l> {
+    element: GenericElement<'l>,
+    cache: RefCell<Option<String>>,
+}
+
+impl<'l> CachedElement<'l> {
+    /// Wrap an element.
+    #[inline]
+    pub fn new(element: GenericElement<'l>) -> Self {
+        CachedElement {
+            element,
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Return the element.
+    #[inline]
+    pub fn get(&self) -> &GenericElement<'l> {
+        &self.element
+    }
+
+    /// Return the element for modification, discarding the serialization.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut GenericElement<'l> {
+        self.cache.get_mut().take();
+        &mut self.element
+    }
+
+    /// Return the element.
+    #[inline]
+    pub fn into_inner(self) -> GenericElement<'l> {
+        self.element
+    }
+
+    /// Serialize the element, reusing the result of the previous call unless
+    /// the element has been borrowed via `get_mut` since.
+    pub fn to_cached_string(&self) -> String {
+        self.cache
+            .borrow_mut()
+            .get_or_insert_with(|| self.element.to_string())
+            .clone()
+    }
+}
+
+impl<'l> From<GenericElement<'l>> for CachedElement<'l> {
+    #[inline]
+    fn from(element: GenericElement<'l>) -> Self {
+        CachedElement::new(element)
+    }
+}
+
+impl<'l> fmt::Display for CachedElement<'l> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut cache = self.cache.borrow_mut();
+        formatter.write_str(cache.get_or_insert_with(|| self.element.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CachedElement;
+    use crate::node::element::GenericElement;
+    use crate::node::{Element, Node, Value};
+
+    #[test]
+    fn element_cached_string() {
+        let mut element = GenericElement::new("foo");
+        element.assign("x", 1);
+        element.append(GenericElement::new("bar"));
+        let mut element = CachedElement::new(element);
+        assert_eq!(element.to_cached_string(), element.get().to_string());
+        assert_eq!(element.to_cached_string(), "<foo x=\"1\">\n<bar/>\n</foo>");
+
+        element.get_mut().assign("x", 2);
+        assert_eq!(element.to_cached_string(), "<foo x=\"2\">\n<bar/>\n</foo>");
+
+        match &mut element.get_mut().get_mut_children()[0] {
+            Node::Element(bar) => bar.set_name("baz"),
+            _ => unreachable!(),
+        }
+        assert_eq!(element.to_string(), "<foo x=\"2\">\n<baz/>\n</foo>");
+        assert_eq!(
+            element.clone().to_cached_string(),
+            element.get().to_string()
+        );
+    }
+
+    #[test]
+    fn element_cached_string_formatting() {
+        let mut element = CachedElement::new(GenericElement::new("g"));
+        element.get_mut().assign("x", Value::from("&amp;"));
+        assert_eq!(element.to_cached_string(), r#"<g x="&amp;amp;"/>"#);
+
+        element.get_mut().assign("x", Value::raw("&amp;"));
+        assert_eq!(element.to_cached_string(), r#"<g x="&amp;"/>"#);
+        assert_eq!(element.to_cached_string(), element.get().to_string());
+    }
+}
@@ -0,0 +1,171 @@
+//! The conversion of shapes into paths.
+
+use super::path::Data;
+use super::{tag, GenericElement, Path};
+use crate::node::{Element, Length};
+
+impl<'l> GenericElement<'l> {
+    /// Convert a basic shape into an equivalent `path` element.
+    ///
+    /// `rect` (including rounded corners), `circle`, `ellipse`, `line`,
+    /// `polyline`, and `polygon` elements are supported, and `None` is
+    /// returned for other elements. The attributes defining the geometry are
+    /// replaced with `d`, and all others are kept. Lengths are taken to be in
+    /// user units.
+    pub fn to_path(&self) -> Option<Path<'l>> {
+        let (data, geometry): (Data, &[&str]) = match &*self.name {
+            tag::Rectangle => (self.rectangle(), &["x", "y", "width", "height", "rx", "ry"]),
+            tag::Circle => {
+                let radius = self.number("r");
+                (self.ellipse(radius, radius), &["cx", "cy", "r"])
+            }
+            tag::Ellipse => {
+                let (rx, ry) = (self.number("rx"), self.number("ry"));
+                (self.ellipse(rx, ry), &["cx", "cy", "rx", "ry"])
+            }
+            tag::Line => (
+                Data::new()
+                    .move_to((self.number("x1"), self.number("y1")))
+                    .line_to((self.number("x2"), self.number("y2"))),
+                &["x1", "y1", "x2", "y2"],
+            ),
+            tag::Polyline => (self.polyline(false), &["points"]),
+            tag::Polygon => (self.polyline(true), &["points"]),
+            _ => return None,
+        };
+        let mut path = Path::new();
+        for (name, value) in self.attributes.iter() {
+            if !geometry.contains(&name.as_str()) {
+                path.assign(name.clone(), value.clone());
+            }
+        }
+        path.assign("d", data);
+        Some(path)
+    }
+
+    fn number(&self, name: &str) -> f64 {
+        self.attributes
+            .get(name)
+            .and_then(|value| Length::parse(value).ok())
+            .map(|length| length.number)
+            .unwrap_or(0.0)
+    }
+
+    fn rectangle(&self) -> Data {
+        let (x, y) = (self.number("x"), self.number("y"));
+        let (width, height) = (self.number("width"), self.number("height"));
+        if width <= 0.0 || height <= 0.0 {
+            return Data::new();
+        }
+        let (rx, ry) = match (self.attributes.get("rx"), self.attributes.get("ry")) {
+            (Some(_), Some(_)) => (self.number("rx"), self.number("ry")),
+            (Some(_), None) => (self.number("rx"), self.number("rx")),
+            (None, Some(_)) => (self.number("ry"), self.number("ry")),
+            (None, None) => (0.0, 0.0),
+        };
+        let (rx, ry) = (rx.clamp(0.0, width / 2.0), ry.clamp(0.0, height / 2.0));
+        if rx == 0.0 || ry == 0.0 {
+            return Data::new()
+                .move_to((x, y))
+                .horizontal_line_to(x + width)
+                .vertical_line_to(y + height)
+                .horizontal_line_to(x)
+                .close();
+        }
+        Data::new()
+            .move_to((x + rx, y))
+            .horizontal_line_to(x + width - rx)
+            .elliptical_arc_to((rx, ry, 0, 0, 1, x + width, y + ry))
+            .vertical_line_to(y + height - ry)
+            .elliptical_arc_to((rx, ry, 0, 0, 1, x + width - rx, y + height))
+            .horizontal_line_to(x + rx)
+            .elliptical_arc_to((rx, ry, 0, 0, 1, x, y + height - ry))
+            .vertical_line_to(y + ry)
+            .elliptical_arc_to((rx, ry, 0, 0, 1, x + rx, y))
+            .close()
+    }
+
+    fn ellipse(&self, rx: f64, ry: f64) -> Data {
+        let (cx, cy) = (self.number("cx"), self.number("cy"));
+        if rx <= 0.0 || ry <= 0.0 {
+            return Data::new();
+        }
+        Data::new()
+            .move_to((cx + rx, cy))
+            .elliptical_arc_to((rx, ry, 0, 0, 1, cx - rx, cy))
+            .elliptical_arc_to((rx, ry, 0, 0, 1, cx + rx, cy))
+            .close()
+    }
+
+    fn polyline(&self, closed: bool) -> Data {
+        let numbers = self
+            .attributes
+            .get("points")
+            .map(|value| {
+                value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|number| !number.is_empty())
+                    .map_while(|number| number.parse::<f64>().ok())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let mut points = numbers.chunks_exact(2);
+        let mut data = match points.next() {
+            Some(point) => Data::new().move_to((point[0], point[1])),
+            _ => return Data::new(),
+        };
+        for point in points {
+            data = data.line_to((point[0], point[1]));
+        }
+        if closed {
+            data = data.close();
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::element::{Circle, Definitions, Polygon, Rectangle};
+
+    #[test]
+    fn circle_to_path() {
+        let circle = Circle::new()
+            .set("cx", 10)
+            .set("cy", 20)
+            .set("r", 5)
+            .set("fill", "red");
+        let path = circle.get_inner().to_path().unwrap();
+        let attributes = path.get_inner().get_attributes();
+
+        assert_eq!(
+            &*attributes["d"],
+            "M15,20 A5,5,0,0,1,5,20 A5,5,0,0,1,15,20 z",
+        );
+        assert_eq!(&*attributes["fill"], "red");
+        assert!(!attributes.contains_key("r"));
+    }
+
+    #[test]
+    fn rectangle_to_path() {
+        let rectangle = Rectangle::new()
+            .set("width", 10)
+            .set("height", 20)
+            .set("rx", 2);
+        let path = rectangle.get_inner().to_path().unwrap();
+
+        assert_eq!(
+            &*path.get_inner().get_attributes()["d"],
+            "M2,0 H8 A2,2,0,0,1,10,2 V18 A2,2,0,0,1,8,20 H2 A2,2,0,0,1,0,18 V2 A2,2,0,0,1,2,0 z",
+        );
+
+        let polygon = Polygon::new().set("points", "0,0 10,0 5,5");
+        let path = polygon.get_inner().to_path().unwrap();
+        assert_eq!(
+            &*path.get_inner().get_attributes()["d"],
+            "M0,0 L10,0 L5,5 z",
+        );
+
+        assert!(Definitions::new().get_inner().to_path().is_none());
+    }
+}
@@ -0,0 +1,56 @@
+//! The stroke dashing.
+
+use crate::node::Value;
+
+/// A dashing of a stroke, which sets the
+/// [`stroke-dasharray`](https://www.w3.org/TR/SVG/painting.html#StrokeDasharrayProperty)
+/// and
+/// [`stroke-dashoffset`](https://www.w3.org/TR/SVG/painting.html#StrokeDashoffsetProperty)
+/// attributes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Dash {
+    pattern: Vec<f64>,
+    offset: Option<f64>,
+}
+
+impl Dash {
+    /// Create a dashing.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the lengths of alternating dashes and gaps.
+    ///
+    /// An odd number of lengths is repeated to yield an even number, which is
+    /// how such a list is interpreted anyway; that is, `[5, 3, 2]` is the same
+    /// as `[5, 3, 2, 5, 3, 2]`. An empty pattern yields a solid stroke.
+    pub fn pattern(mut self, pattern: &[f64]) -> Self {
+        self.pattern = pattern.to_vec();
+        if pattern.len() % 2 == 1 {
+            self.pattern.extend_from_slice(pattern);
+        }
+        self
+    }
+
+    /// Set the distance into the pattern at which dashing starts.
+    #[inline]
+    pub fn offset(mut self, offset: f64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Return the attributes to assign.
+    pub(crate) fn attributes(&self) -> Vec<(&'static str, Value)> {
+        let pattern = if self.pattern.is_empty() {
+            Value::from("none")
+        } else {
+            Value::from(self.pattern.clone())
+        };
+        let mut attributes = vec![("stroke-dasharray", pattern)];
+        if let Some(offset) = self.offset {
+            attributes.push(("stroke-dashoffset", Value::from(offset)));
+        }
+        attributes
+    }
+}
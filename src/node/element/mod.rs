@@ -8,23 +8,38 @@ use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::io;
 use std::iter::once;
+use std::sync::Arc;
 
-use crate::events::Event;
+use crate::events::composer::Options;
+use crate::events::{CompactEvent, Event};
 use crate::node::element::tag::Type;
 use crate::node::{Attributes, Children, Element, Node, Value};
 use crate::Composer;
 use std::convert::TryFrom;
 
+pub use self::cached::CachedElement;
+pub use self::dash::Dash;
+pub use self::text::{FontMetrics, TextLayout, DEFAULT_FONT_SIZE};
+
+mod cached;
+mod conversion;
+mod dash;
 pub mod path;
 pub mod tag;
+mod text;
 
 /// An element.
+///
+/// The children are shared between clones until either clone modifies them,
+/// at which point they are copied. Cloning an element is therefore cheap, and
+/// the clones still behave as independent values.
 #[derive(Clone, Debug)]
 pub struct GenericElement<'l> {
     name: Cow<'l, str>,
     attributes: Attributes,
-    children: Children<'l>,
+    children: Arc<Children<'l>>,
 }
 
 impl<'l> GenericElement<'l> {
@@ -36,7 +51,7 @@ impl<'l> GenericElement<'l> {
         GenericElement {
             name: name.into(),
             attributes: Attributes::new(),
-            children: Children::new(),
+            children: Arc::new(Children::new()),
         }
     }
 
@@ -45,7 +60,7 @@ impl<'l> GenericElement<'l> {
         Self {
             name,
             attributes,
-            children,
+            children: Arc::new(children),
         }
     }
 
@@ -54,6 +69,7 @@ impl<'l> GenericElement<'l> {
         &self.name
     }
 
+    /// Rename the element, which affects both its start and end tags.
     #[inline]
     pub fn set_name<T: Into<Cow<'l, str>>>(&mut self, name: T) {
         self.name = name.into();
@@ -76,7 +92,245 @@ impl<'l> GenericElement<'l> {
 
     #[inline]
     pub fn get_mut_children(&mut self) -> &mut Children<'l> {
-        &mut self.children
+        Arc::make_mut(&mut self.children)
+    }
+
+    /// Return the first child.
+    #[inline]
+    pub fn first_child(&self) -> Option<&Node<'l>> {
+        self.children.first()
+    }
+
+    /// Return the last child.
+    #[inline]
+    pub fn last_child(&self) -> Option<&Node<'l>> {
+        self.children.last()
+    }
+
+    /// Return the child at a position starting from zero.
+    #[inline]
+    pub fn nth_child(&self, n: usize) -> Option<&Node<'l>> {
+        self.children.get(n)
+    }
+
+    /// Return the number of children.
+    #[inline]
+    pub fn child_count(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Pass the element through a function, which keeps a chain of builder
+    /// calls going.
+    #[inline]
+    pub fn apply<F>(self, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        f(self)
+    }
+
+    /// Pass the element through a function if the condition holds.
+    #[inline]
+    pub fn apply_if<F>(self, condition: bool, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if condition {
+            f(self)
+        } else {
+            self
+        }
+    }
+
+    /// Check if the children are shared with another element, which is the
+    /// case for clones that have not modified their children yet.
+    #[inline]
+    pub fn shares_children(&self, other: &GenericElement) -> bool {
+        Arc::ptr_eq(&self.children, &other.children)
+    }
+
+    /// Merge adjacent text nodes and remove empty ones throughout the subtree,
+    /// similarly to `normalize` in the DOM.
+    ///
+    /// Text nodes hold markup, so entities are left as they are.
+    pub fn normalize_text(&mut self) {
+        let children = self.get_mut_children();
+        let mut normalized: Children<'l> = Vec::with_capacity(children.len());
+        for child in children.drain(..) {
+            match (normalized.last_mut(), child) {
+                (_, Node::Text(content)) if content.is_empty() => {}
+                (Some(Node::Text(previous)), Node::Text(content)) => {
+                    previous.to_mut().push_str(&content);
+                }
+                (_, Node::Element(mut element)) => {
+                    element.normalize_text();
+                    normalized.push(Node::Element(element));
+                }
+                (_, child) => normalized.push(child),
+            }
+        }
+        *children = normalized;
+    }
+
+    /// Write the element and its descendants with the given options.
+    pub fn write_to<T>(&self, destination: T, options: Options) -> io::Result<()>
+    where
+        T: io::Write,
+    {
+        let mut composer = Composer::with_options(destination, options);
+        self.to_compact_events()
+            .try_for_each(|event| composer.write_compact_event(event))?;
+        composer.flush()
+    }
+
+    /// Clone the element without sharing the children with the original at
+    /// any depth.
+    pub(crate) fn deep_clone(&self) -> Self {
+        let children = self
+            .children
+            .iter()
+            .map(|child| match child {
+                Node::Element(element) => Node::Element(element.deep_clone()),
+                _ => child.clone(),
+            })
+            .collect();
+        GenericElement::new_from(self.name.clone(), self.attributes.clone(), children)
+    }
+
+    /// Declare a namespace by assigning an `xmlns` attribute for the default
+    /// namespace or an `xmlns:prefix` attribute for a prefixed one.
+    pub fn declare_namespace(&mut self, prefix: Option<&str>, uri: &str) {
+        let name = match prefix {
+            Some(prefix) => format!("xmlns:{}", prefix),
+            None => "xmlns".into(),
+        };
+        self.assign(name, uri);
+    }
+
+    /// Return the namespaces declared on the element as pairs of prefixes and
+    /// URIs, where the default namespace has no prefix.
+    pub fn namespaces(&self) -> Vec<(Option<&str>, &str)> {
+        let mut namespaces = self
+            .attributes
+            .iter()
+            .filter_map(|(name, value)| match name.as_str() {
+                "xmlns" => Some((None, &**value)),
+                _ => name
+                    .strip_prefix("xmlns:")
+                    .map(|prefix| (Some(prefix), &**value)),
+            })
+            .collect::<Vec<_>>();
+        namespaces.sort();
+        namespaces
+    }
+
+    /// Assign the `stroke-dasharray` and `stroke-dashoffset` attributes.
+    pub fn set_dash(&mut self, dash: Dash) {
+        for (name, value) in dash.attributes() {
+            self.assign(name, value);
+        }
+    }
+
+    /// Assign a presence-only attribute, such as `hidden`.
+    ///
+    /// The attribute is written with its own name as the value, for example,
+    /// `hidden="hidden"`, unless the composer is set to write bare names.
+    #[inline]
+    pub fn set_flag(&mut self, name: &str) {
+        self.assign(name, Value::flag(name));
+    }
+
+    /// Assign the `clip-path` attribute referring to the element with the
+    /// given `id`.
+    #[inline]
+    pub fn set_clip_path(&mut self, id: &str) {
+        self.assign("clip-path", format!("url(#{})", id));
+    }
+
+    /// Return the `id` of the element referred to by the `clip-path`
+    /// attribute.
+    #[inline]
+    pub fn get_clip_path(&self) -> Option<&str> {
+        self.attributes
+            .get("clip-path")
+            .and_then(|value| reference(value))
+    }
+
+    /// Assign the `mask` attribute referring to the element with the given
+    /// `id`.
+    #[inline]
+    pub fn set_mask(&mut self, id: &str) {
+        self.assign("mask", format!("url(#{})", id));
+    }
+
+    /// Return the `id` of the element referred to by the `mask` attribute.
+    #[inline]
+    pub fn get_mask(&self) -> Option<&str> {
+        self.attributes
+            .get("mask")
+            .and_then(|value| reference(value))
+    }
+
+    /// Return the custom data attribute `data-{key}`.
+    #[inline]
+    pub fn data(&self, key: &str) -> Option<&Value> {
+        self.attributes.get(&format!("data-{}", key))
+    }
+
+    /// Assign the `requiredFeatures` attribute, which is a conditional
+    /// processing attribute evaluated by `switch` elements.
+    pub fn set_required_features(&mut self, features: &[&str]) {
+        self.assign("requiredFeatures", features.join(" "));
+    }
+
+    /// Assign the `requiredExtensions` attribute, which is a conditional
+    /// processing attribute evaluated by `switch` elements.
+    pub fn set_required_extensions(&mut self, extensions: &[&str]) {
+        self.assign("requiredExtensions", extensions.join(" "));
+    }
+
+    /// Assign the `systemLanguage` attribute, which is a conditional
+    /// processing attribute evaluated by `switch` elements.
+    pub fn set_system_language(&mut self, languages: &[&str]) {
+        self.assign("systemLanguage", languages.join(", "));
+    }
+
+    /// Find an element by its `id` attribute, including the element itself.
+    pub fn get_element_by_id(&self, id: &str) -> Option<&GenericElement<'l>> {
+        if self.attributes.get("id").map(|value| &**value) == Some(id) {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| match child {
+            Node::Element(element) => element.get_element_by_id(id),
+            _ => None,
+        })
+    }
+
+    /// Return the events of the element and its descendants, borrowing the
+    /// attributes instead of copying them as `to_events` does.
+    pub fn to_compact_events(&'l self) -> Box<dyn Iterator<Item = CompactEvent<'l>> + 'l> {
+        if self.children.is_empty() {
+            Box::new(once(CompactEvent::Tag(
+                &self.name,
+                Type::Empty,
+                &self.attributes,
+            )))
+        } else {
+            let child_events = self
+                .children
+                .iter()
+                .flat_map(|child| child.to_compact_events());
+
+            Box::new(
+                once(CompactEvent::Tag(&self.name, Type::Start, &self.attributes))
+                    .chain(child_events)
+                    .chain(once(CompactEvent::Tag(
+                        &self.name,
+                        Type::End,
+                        &self.attributes,
+                    ))),
+            )
+        }
     }
 
     pub fn to_events(&'l self) -> Box<dyn Iterator<Item = Event<'l>> + 'l> {
@@ -96,6 +350,31 @@ impl<'l> GenericElement<'l> {
             )
         }
     }
+
+    /// Convert the element into events like `to_events` does but with a start
+    /// and an end tag for every element, including those without children.
+    pub fn to_events_explicit(&'l self) -> Box<dyn Iterator<Item = Event<'l>> + 'l> {
+        let child_events = self.children.iter().flat_map(|child| match child {
+            Node::Element(element) => element.to_events_explicit(),
+            _ => child.to_events(),
+        });
+
+        Box::new(
+            once(Event::Tag(&self.name, Type::Start, self.attributes.clone()))
+                .chain(child_events)
+                .chain(once(Event::Tag(&self.name, Type::End, HashMap::new()))),
+        )
+    }
+}
+
+// https://www.w3.org/TR/css-values-3/#urls
+fn reference(value: &str) -> Option<&str> {
+    let value = value.trim().strip_prefix("url(")?.strip_suffix(')')?.trim();
+    let value = ['"', '\'']
+        .iter()
+        .find_map(|&quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value);
+    value.strip_prefix('#').filter(|id| !id.is_empty())
 }
 
 impl<'l> TryFrom<Node<'l>> for GenericElement<'l> {
@@ -109,12 +388,32 @@ impl<'l> TryFrom<Node<'l>> for GenericElement<'l> {
     }
 }
 
+impl<'a, 'l> IntoIterator for &'a GenericElement<'l> {
+    type Item = &'a Node<'l>;
+    type IntoIter = std::slice::Iter<'a, Node<'l>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.iter()
+    }
+}
+
+impl<'a, 'l> IntoIterator for &'a mut GenericElement<'l> {
+    type Item = &'a mut Node<'l>;
+    type IntoIter = std::slice::IterMut<'a, Node<'l>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.get_mut_children().iter_mut()
+    }
+}
+
 impl<'l> fmt::Display for GenericElement<'l> {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         let mut displayed = Vec::new();
         let mut composer = Composer::new(&mut displayed);
-        self.to_events()
-            .try_for_each(|event| composer.write_event(&event))
+        self.to_compact_events()
+            .try_for_each(|event| composer.write_compact_event(event))
             .map_err(|_error| fmt::Error)?;
         write!(formatter, "{}", String::from_utf8_lossy(&displayed))
     }
@@ -126,7 +425,7 @@ impl<'l> Element<'l> for GenericElement<'l> {
     where
         T: Into<Node<'l>>,
     {
-        self.children.push(node.into());
+        self.get_mut_children().push(node.into());
     }
 
     #[inline]
@@ -139,14 +438,53 @@ impl<'l> Element<'l> for GenericElement<'l> {
     }
 }
 
-macro_rules! implement {
-    ($(#[$doc:meta] struct $struct_name:ident)*) => ($(
+/// Define a typed element as a wrapper around a generic element, which can be
+/// borrowed as the typed element.
+macro_rules! wrapper {
+    (#[$doc:meta] struct $struct_name:ident) => {
         #[$doc]
         #[derive(Clone, Debug)]
+        #[repr(transparent)]
         pub struct $struct_name<'l> {
             inner: GenericElement<'l>,
         }
 
+        const _: () = assert!(
+            std::mem::size_of::<$struct_name>() == std::mem::size_of::<GenericElement>()
+                && std::mem::align_of::<$struct_name>() == std::mem::align_of::<GenericElement>()
+        );
+
+        impl<'l> $struct_name<'l> {
+            /// Borrow an element as this type, provided it has the matching
+            /// name.
+            pub fn from_ref<'a>(element: &'a GenericElement<'l>) -> Option<&'a Self> {
+                if element.get_name() != tag::$struct_name {
+                    return None;
+                }
+                // SAFETY: The type is `#[repr(transparent)]` over its only
+                // field, a `GenericElement`, so the two have the same layout,
+                // and the reference keeps the lifetime of the borrow.
+                Some(unsafe { &*(element as *const GenericElement<'l> as *const Self) })
+            }
+
+            /// Borrow an element mutably as this type, provided it has the
+            /// matching name.
+            pub fn from_mut<'a>(element: &'a mut GenericElement<'l>) -> Option<&'a mut Self> {
+                if element.get_name() != tag::$struct_name {
+                    return None;
+                }
+                // SAFETY: The layouts are the same as for `from_ref`, and the
+                // exclusive borrow is passed on to the result.
+                Some(unsafe { &mut *(element as *mut GenericElement<'l> as *mut Self) })
+            }
+        }
+    };
+}
+
+macro_rules! implement {
+    ($(#[$doc:meta] struct $struct_name:ident)*) => ($(
+        wrapper! { #[$doc] struct $struct_name }
+
         impl<'l> $struct_name<'l> {
             /// Create a node.
             #[inline]
@@ -251,6 +589,9 @@ implement! {
     #[doc = "A [`mask`](https://www.w3.org/TR/SVG/masking.html#MaskElement) element."]
     struct Mask
 
+    #[doc = "A [`metadata`](https://www.w3.org/TR/SVG/struct.html#MetadataElement) element."]
+    struct Metadata
+
     #[doc = "An [`mpath`](https://www.w3.org/TR/SVG/animate.html#MPathElement) element."]
     struct MotionPath
 
@@ -275,6 +616,9 @@ implement! {
     #[doc = "A [`stop`](https://www.w3.org/TR/SVG/pservers.html#StopElement) element."]
     struct Stop
 
+    #[doc = "A [`switch`](https://www.w3.org/TR/SVG/struct.html#SwitchElement) element."]
+    struct Switch
+
     #[doc = "A [`symbol`](https://www.w3.org/TR/SVG/struct.html#SymbolElement) element."]
     struct Symbol
 
@@ -298,11 +642,7 @@ macro_rules! implement {
         struct $struct_name:ident
         [$($pn:ident: $($pt:path)*),*] [$inner:ident $(,$an:ident: $at:ty)*] $body:block
     )*) => ($(
-        #[$doc]
-        #[derive(Clone, Debug)]
-        pub struct $struct_name<'l> {
-            inner: GenericElement<'l>,
-        }
+        wrapper! { #[$doc] struct $struct_name }
 
         implement! { @itemize
             impl<'l> $struct_name<'l> {
@@ -347,10 +687,197 @@ implement! {
     }
 }
 
+impl<'l> Path<'l> {
+    /// Parse the `d` attribute.
+    ///
+    /// A missing attribute is treated as empty data.
+    pub fn data(&self) -> crate::events::parser::Result<path::Data> {
+        match self.inner.attributes.get("d") {
+            Some(value) => path::Data::parse(value),
+            None => Ok(path::Data::new()),
+        }
+    }
+
+    /// Assign the `d` attribute.
+    #[inline]
+    pub fn set_data(&mut self, data: path::Data) {
+        self.inner.assign("d", data);
+    }
+
+    /// Create a path drawn as a line, that is, with `fill="none"`,
+    /// `stroke="currentColor"`, and `stroke-width="1"`, which the other
+    /// paint builders and `set` can override.
+    #[inline]
+    pub fn styled() -> Self {
+        Path::new()
+            .fill_none()
+            .stroke("currentColor")
+            .stroke_width(1)
+    }
+
+    /// Assign the `stroke` attribute.
+    #[inline]
+    pub fn stroke<T: Into<Value>>(self, color: T) -> Self {
+        self.set("stroke", color)
+    }
+
+    /// Assign the `stroke-width` attribute.
+    #[inline]
+    pub fn stroke_width<T: Into<Value>>(self, width: T) -> Self {
+        self.set("stroke-width", width)
+    }
+
+    /// Assign `none` to the `fill` attribute.
+    #[inline]
+    pub fn fill_none(self) -> Self {
+        self.set("fill", "none")
+    }
+}
+
+impl<'l> Marker<'l> {
+    /// Create a triangular arrowhead.
+    ///
+    /// The marker has a `viewBox` of `0 0 10 10` with the reference point in
+    /// the middle, is sized at six times the stroke width, and is oriented
+    /// with `auto-start-reverse`, so that it points along the path at its end
+    /// and backwards at its start. It is filled with `context-stroke`, that
+    /// is, the color of the stroke of the path.
+    pub fn arrowhead(id: &str) -> Self {
+        Marker::new()
+            .set("id", id)
+            .set("viewBox", (0, 0, 10, 10))
+            .set("refX", 5)
+            .set("refY", 5)
+            .set("markerWidth", 6)
+            .set("markerHeight", 6)
+            .set("orient", "auto-start-reverse")
+            .add(
+                Path::new()
+                    .set(
+                        "d",
+                        path::Data::new()
+                            .move_to((0, 0))
+                            .line_to((10, 5))
+                            .line_to((0, 10))
+                            .close(),
+                    )
+                    .set("fill", "context-stroke"),
+            )
+    }
+
+    /// Return a reference to the marker suitable for the `marker-start`,
+    /// `marker-mid`, and `marker-end` attributes, provided it has an `id`.
+    pub fn url(&self) -> Option<String> {
+        let id = self.inner.attributes.get("id")?;
+        Some(format!("url(#{})", &**id))
+    }
+}
+
+impl<'l> Pattern<'l> {
+    /// Create a tile repeated every `width` by `height` user units.
+    ///
+    /// The tile is positioned at the origin of the user coordinate system of
+    /// the element it is applied to, and its content is drawn in the same
+    /// units.
+    pub fn tile<T, U>(id: &str, width: T, height: U) -> Self
+    where
+        T: Into<Value>,
+        U: Into<Value>,
+    {
+        Pattern::new()
+            .set("id", id)
+            .set("width", width)
+            .set("height", height)
+            .set("patternUnits", "userSpaceOnUse")
+    }
+
+    /// Append content to the tile.
+    #[inline]
+    pub fn with_content<T>(self, node: T) -> Self
+    where
+        T: Into<Node<'l>>,
+    {
+        self.add(node)
+    }
+}
+
+macro_rules! implement {
+    ($($struct_name:ident,)*) => (
+        /// An element converted into its typed wrapper.
+        #[derive(Clone, Debug)]
+        pub enum TypedElement<'l> {
+            $(
+                #[doc = concat!("A [`", stringify!($struct_name), "`] element.")]
+                $struct_name($struct_name<'l>),
+            )*
+            /// An element without a typed wrapper.
+            Generic(GenericElement<'l>),
+        }
+
+        /// Convert an element into its typed wrapper based on its name.
+        ///
+        /// Elements with unknown names are returned as `TypedElement::Generic`.
+        pub fn downcast(element: GenericElement) -> TypedElement {
+            match element.get_name() {
+                $(tag::$struct_name => TypedElement::$struct_name($struct_name { inner: element }),)*
+                _ => TypedElement::Generic(element),
+            }
+        }
+    );
+}
+
+implement! {
+    Animate,
+    AnimateColor,
+    AnimateMotion,
+    AnimateTransform,
+    Circle,
+    ClipPath,
+    Definitions,
+    Description,
+    Ellipse,
+    Filter,
+    ForeignObject,
+    Group,
+    Image,
+    Line,
+    LinearGradient,
+    Link,
+    Marker,
+    Mask,
+    Metadata,
+    MotionPath,
+    Path,
+    Pattern,
+    Polygon,
+    Polyline,
+    RadialGradient,
+    Rectangle,
+    Script,
+    Stop,
+    Style,
+    SVG,
+    Switch,
+    Symbol,
+    Text,
+    TextPath,
+    Title,
+    Use,
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{GenericElement, Style};
-    use crate::node::Element;
+    use std::convert::TryFrom;
+
+    use super::path::Data;
+    use super::tag::Type;
+    use super::{
+        downcast, Circle, Dash, GenericElement, Group, Line, Marker, Options, Path, Pattern,
+        Rectangle, Style, Switch, Text, TypedElement,
+    };
+    use crate::events::{CompactEvent, Event};
+    use crate::node::{Color, Element, Node};
+    use crate::{Document, Parser};
 
     #[test]
     fn element_display() {
@@ -377,7 +904,260 @@ mod tests {
         element.assign("d", r#""double""#);
         element.assign("m", r#""mixed'"#);
 
-        assert_eq!(element.to_string(), r#"<foo d='"double"' s="'single'"/>"#);
+        assert_eq!(
+            element.to_string(),
+            r#"<foo d='"double"' m="&quot;mixed'" s="'single'"/>"#
+        );
+    }
+
+    #[test]
+    fn element_children() {
+        fn name<'a>(node: Option<&'a Node>) -> Option<&'a str> {
+            node.and_then(Node::as_element)
+                .map(GenericElement::get_name)
+        }
+
+        let element = GenericElement::new("g");
+        assert!(element.first_child().is_none());
+        assert!(element.last_child().is_none());
+        assert!(element.nth_child(0).is_none());
+        assert_eq!(element.child_count(), 0);
+
+        let element = GenericElement::from(Group::new().add(Circle::new()));
+        assert_eq!(name(element.first_child()), Some("circle"));
+        assert_eq!(name(element.last_child()), Some("circle"));
+        assert!(element.nth_child(1).is_none());
+        assert_eq!(element.child_count(), 1);
+
+        let element = GenericElement::from(
+            Group::new()
+                .add(Circle::new())
+                .add(Line::new())
+                .add(Rectangle::new()),
+        );
+        assert_eq!(name(element.first_child()), Some("circle"));
+        assert_eq!(name(element.nth_child(1)), Some("line"));
+        assert_eq!(name(element.last_child()), Some("rect"));
+        assert!(element.nth_child(3).is_none());
+        assert_eq!(element.child_count(), 3);
+    }
+
+    #[test]
+    fn element_to_events_explicit() {
+        let element = GenericElement::from(Group::new().add(Group::new()));
+        let events = element.to_events_explicit().collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[0], Event::Tag("g", Type::Start, _)));
+        assert!(matches!(events[1], Event::Tag("g", Type::Start, _)));
+        assert!(matches!(events[2], Event::Tag("g", Type::End, _)));
+        assert!(matches!(events[3], Event::Tag("g", Type::End, _)));
+        assert!(matches!(
+            element
+                .nth_child(0)
+                .unwrap()
+                .to_events()
+                .collect::<Vec<_>>()[..],
+            [Event::Tag("g", Type::Empty, _)],
+        ));
+    }
+
+    #[test]
+    fn path_styled() {
+        let path = Path::styled()
+            .stroke(Color::Rgb(255, 0, 0))
+            .stroke_width(2.5)
+            .set("d", "M0,0 L1,1");
+
+        assert_eq!(
+            path.to_string(),
+            r##"<path d="M0,0 L1,1" fill="none" stroke="#ff0000" stroke-width="2.5"/>"##,
+        );
+        assert_eq!(
+            Path::styled().to_string(),
+            r#"<path fill="none" stroke="currentColor" stroke-width="1"/>"#,
+        );
+    }
+
+    #[test]
+    fn element_apply_if() {
+        let build = |dashed| {
+            Line::new()
+                .set("x2", 10)
+                .apply_if(dashed, |line| line.set("stroke-dasharray", 2))
+                .apply(|line| line.set("y2", 10))
+        };
+
+        assert_eq!(
+            build(true).to_string(),
+            r#"<line stroke-dasharray="2" x2="10" y2="10"/>"#
+        );
+        assert_eq!(build(false).to_string(), r#"<line x2="10" y2="10"/>"#);
+
+        let element = GenericElement::new("foo").apply_if(true, |mut element| {
+            element.assign("x", 1);
+            element
+        });
+        assert_eq!(element.to_string(), r#"<foo x="1"/>"#);
+    }
+
+    #[test]
+    fn switch_display() {
+        let switch = Switch::new()
+            .add(
+                Text::new()
+                    .set_system_language(&["de", "fr"])
+                    .add(Node::Text("Hallo".into())),
+            )
+            .add(
+                Text::new()
+                    .set_system_language(&["en-US"])
+                    .add(Node::Text("Hello".into())),
+            )
+            .add(Text::new().add(Node::Text("Hi".into())));
+
+        assert_eq!(
+            switch.to_string(),
+            "<switch>\n\
+             <text systemLanguage=\"de, fr\">\nHallo\n</text>\n\
+             <text systemLanguage=\"en-US\">\nHello\n</text>\n\
+             <text>\nHi\n</text>\n\
+             </switch>",
+        );
+    }
+
+    #[test]
+    fn element_set_name() {
+        let mut document =
+            Document::parse(r##"<svg><image href="#a"><title>A</title></image></svg>"##).unwrap();
+        for child in document.get_mut_svg().get_mut_children() {
+            if let Node::Element(child) = child {
+                child.set_name("use");
+            }
+        }
+
+        assert_eq!(
+            document.get_svg().to_string(),
+            "<svg>\n<use href=\"#a\">\n<title>\nA\n</title>\n</use>\n</svg>",
+        );
+    }
+
+    #[test]
+    fn element_write_to() {
+        let document = Document::parse(
+            r#"<svg><g id="icon" fill="red"><circle r="1"/><rect width="2" height="2"/></g></svg>"#,
+        )
+        .unwrap();
+        let group = document.get_element_by_id("icon").unwrap();
+
+        let mut destination = Vec::new();
+        group
+            .write_to(&mut destination, Options::default())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(destination).unwrap(),
+            "<g fill=\"red\" id=\"icon\">\n<circle r=\"1\"/>\n<rect height=\"2\" width=\"2\"/>\n</g>",
+        );
+
+        let options = Options {
+            max_line_width: Some(20),
+            ..Options::default()
+        };
+        let mut destination = Vec::new();
+        group.write_to(&mut destination, options).unwrap();
+        assert_eq!(
+            String::from_utf8(destination).unwrap(),
+            "<g fill=\"red\"\n   id=\"icon\">\n<circle r=\"1\"/>\n<rect height=\"2\"\n      width=\"2\"/>\n</g>",
+        );
+    }
+
+    #[test]
+    fn pattern_tile() {
+        let pattern = Pattern::tile("dots", 10, 10)
+            .with_content(Circle::new().set("cx", 5).set("cy", 5).set("r", 2));
+        let rectangle = Rectangle::new()
+            .set("width", 100)
+            .set("height", 100)
+            .fill_pattern("dots");
+
+        assert_eq!(
+            pattern.to_string(),
+            "<pattern height=\"10\" id=\"dots\" patternUnits=\"userSpaceOnUse\" width=\"10\">\n\
+             <circle cx=\"5\" cy=\"5\" r=\"2\"/>\n\
+             </pattern>",
+        );
+        assert_eq!(
+            rectangle.to_string(),
+            "<rect fill=\"url(#dots)\" height=\"100\" width=\"100\"/>",
+        );
+    }
+
+    #[test]
+    fn element_flag() {
+        let mut element = GenericElement::new("foo");
+        element.set_flag("hidden");
+        element.assign("x", 1);
+        assert_eq!(element.to_string(), r#"<foo hidden="hidden" x="1"/>"#);
+
+        let options = Options {
+            bare_flags: true,
+            ..Options::default()
+        };
+        let mut destination = Vec::new();
+        element.write_to(&mut destination, options).unwrap();
+        assert_eq!(
+            String::from_utf8(destination).unwrap(),
+            r#"<foo hidden x="1"/>"#
+        );
+    }
+
+    #[test]
+    fn element_borrow_typed() {
+        let node = Node::from(Path::new().set("d", "M0,0 L1,1"));
+        let path = node.as_element().and_then(Path::from_ref).unwrap();
+        assert_eq!(
+            &**path.get_inner().get_attributes().get("d").unwrap(),
+            "M0,0 L1,1"
+        );
+        assert_eq!(path.data().unwrap().len(), 2);
+
+        assert!(node.as_element().and_then(Line::from_ref).is_none());
+        assert!(Node::new_text("foo").as_element().is_none());
+    }
+
+    #[test]
+    fn element_data() {
+        let element = GenericElement::from(Rectangle::new().set("data-value", 42));
+        assert_eq!(&**element.data("value").unwrap(), "42");
+        assert_eq!(element.data("other"), None);
+
+        let content = r#"<svg data-json="{&quot;a&quot;: [1]}" foo:bar="baz" x-custom="1">
+<rect data-value="42"/>
+</svg>"#;
+        let document = Document::parse(content).unwrap();
+        let svg = document.get_svg();
+        assert_eq!(&**svg.data("json").unwrap(), r#"{"a": [1]}"#);
+        assert_eq!(svg.to_string(), content);
+    }
+
+    #[test]
+    fn element_clip_path() {
+        let mut element = GenericElement::new("rect");
+        assert_eq!(element.get_clip_path(), None);
+
+        element.set_clip_path("clip");
+        element.set_mask("mask");
+        assert_eq!(
+            &**element.get_attributes().get("clip-path").unwrap(),
+            "url(#clip)"
+        );
+        assert_eq!(element.get_clip_path(), Some("clip"));
+        assert_eq!(element.get_mask(), Some("mask"));
+
+        element.assign("clip-path", "url( '#other' )");
+        assert_eq!(element.get_clip_path(), Some("other"));
+        element.assign("clip-path", "none");
+        assert_eq!(element.get_clip_path(), None);
     }
 
     #[test]
@@ -386,10 +1166,180 @@ mod tests {
 
         assert_eq!(
             element.to_string(),
-            "<style>\n\
-             * { font-family: foo; }\n\
-             </style>\
-             "
+            "<style>* { font-family: foo; }</style>"
+        );
+    }
+
+    #[test]
+    fn element_normalize_text() {
+        let mut element = GenericElement::new("text");
+        element.append(Node::new_text("foo "));
+        element.append(Node::new_text(""));
+        element.append(Node::new_text("&amp; bar"));
+        let mut span = GenericElement::new("tspan");
+        span.append(Node::new_text("a"));
+        span.append(Node::new_text("b"));
+        element.append(span);
+        element.normalize_text();
+
+        let children = element.get_children();
+        assert_eq!(children.len(), 2);
+        assert!(matches!(&children[0], Node::Text(content) if content == "foo &amp; bar"));
+        match &children[1] {
+            Node::Element(span) => {
+                assert_eq!(span.get_children().len(), 1);
+                assert!(matches!(&span.get_children()[0], Node::Text(content) if content == "ab"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn marker_arrowhead() {
+        let marker = Marker::arrowhead("arrow");
+        assert_eq!(marker.url().unwrap(), "url(#arrow)");
+
+        let marker = marker.get_inner();
+        assert_eq!(&*marker.get_attributes()["id"], "arrow");
+        assert_eq!(marker.get_children().len(), 1);
+        match &marker.get_children()[0] {
+            Node::Element(path) => {
+                assert_eq!(path.get_name(), "path");
+                assert_eq!(&*path.get_attributes()["d"], "M0,0 L10,5 L0,10 z");
+            }
+            _ => unreachable!(),
+        }
+
+        let path = Path::new().set("marker-end", Marker::arrowhead("arrow").url().unwrap());
+        assert_eq!(
+            &*path.get_inner().get_attributes()["marker-end"],
+            "url(#arrow)"
+        );
+    }
+
+    #[test]
+    fn element_downcast() {
+        let mut document =
+            Document::from_event_parser(Parser::new("<svg><circle r='1'/></svg>")).unwrap();
+        let svg = document.get_mut_svg();
+        let element = GenericElement::try_from(svg.get_mut_children().remove(0)).unwrap();
+        match downcast(element) {
+            TypedElement::Circle(circle) => assert_eq!(circle.get_inner().get_name(), "circle"),
+            _ => unreachable!(),
+        }
+
+        let element = GenericElement::new("foo");
+        match downcast(element) {
+            TypedElement::Generic(element) => assert_eq!(element.get_name(), "foo"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn element_set_attr() {
+        let mut document =
+            Document::from_event_parser(Parser::new("<svg><circle r='1'/><g/></svg>")).unwrap();
+        for child in document.get_mut_svg().get_mut_children() {
+            if let Some(circle) = child.as_mut_element().and_then(Circle::from_mut) {
+                circle.set_attr("r", 2);
+            }
+        }
+        assert_eq!(
+            document.get_svg().to_string(),
+            "<svg>\n<circle r=\"2\"/>\n<g/>\n</svg>",
         );
     }
+
+    #[test]
+    fn path_data() {
+        let data = Data::new().move_to((1, 2)).line_by((3, 4)).close();
+        let mut path = Path::new().set("d", data);
+        assert_eq!(path.data().unwrap().len(), 3);
+
+        path.set_data(Data::new().move_to((5, 6)));
+        assert_eq!(path.data().unwrap().len(), 1);
+        assert_eq!(Path::new().data().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn element_into_iterator() {
+        let mut group = GenericElement::new("g");
+        group.append(GenericElement::new("rect"));
+        group.append(GenericElement::new("circle"));
+
+        for child in &mut group {
+            if let Node::Element(element) = child {
+                element.assign("fill", "red");
+            }
+        }
+
+        let mut names = Vec::new();
+        for child in &group {
+            match child {
+                Node::Element(element) => {
+                    assert_eq!(&*element.get_attributes()["fill"], "red");
+                    names.push(element.get_name());
+                }
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(names, &["rect", "circle"]);
+    }
+
+    #[test]
+    fn element_dash() {
+        let mut element = GenericElement::new("line");
+        element.set_dash(Dash::new().pattern(&[4.0, 2.0]));
+        assert_eq!(element.to_string(), r#"<line stroke-dasharray="4 2"/>"#);
+
+        let line = Line::new().set_dash(Dash::new().pattern(&[5.0, 3.0, 2.0]).offset(1.5));
+        assert_eq!(
+            line.to_string(),
+            r#"<line stroke-dasharray="5 3 2 5 3 2" stroke-dashoffset="1.5"/>"#
+        );
+    }
+
+    #[test]
+    fn element_namespaces() {
+        let mut element = GenericElement::new("svg");
+        element.declare_namespace(None, "http://www.w3.org/2000/svg");
+        element.declare_namespace(Some("xlink"), "http://www.w3.org/1999/xlink");
+        element.assign("x", 1);
+
+        assert_eq!(
+            &*element.get_attributes()["xmlns:xlink"],
+            "http://www.w3.org/1999/xlink"
+        );
+        assert_eq!(
+            element.namespaces(),
+            vec![
+                (None, "http://www.w3.org/2000/svg"),
+                (Some("xlink"), "http://www.w3.org/1999/xlink"),
+            ],
+        );
+    }
+
+    #[test]
+    fn element_compact_events() {
+        let mut element = GenericElement::new("g");
+        element.assign("id", "foo");
+        element.append(GenericElement::new("rect"));
+
+        let events = element.to_compact_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 3);
+        match (events[0], events[1]) {
+            (
+                CompactEvent::Tag("g", Type::Start, first),
+                CompactEvent::Tag("rect", Type::Empty, second),
+            ) => {
+                assert!(std::ptr::eq(first, element.get_attributes()));
+                match &element.get_children()[0] {
+                    Node::Element(child) => assert!(std::ptr::eq(second, child.get_attributes())),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+        assert!(matches!(events[2], CompactEvent::Tag("g", Type::End, _)));
+    }
 }
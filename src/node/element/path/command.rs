@@ -59,6 +59,27 @@ pub enum Command {
     Close,
 }
 
+impl Command {
+    /// Return the positioning of the command, which is `None` for
+    /// `Command::Close` as it has no parameters.
+    pub fn position(&self) -> Option<Position> {
+        use self::Command::*;
+
+        match self {
+            Move(position, _)
+            | Line(position, _)
+            | HorizontalLine(position, _)
+            | VerticalLine(position, _)
+            | QuadraticCurve(position, _)
+            | SmoothQuadraticCurve(position, _)
+            | CubicCurve(position, _)
+            | SmoothCubicCurve(position, _)
+            | EllipticalArc(position, _) => Some(*position),
+            Close => None,
+        }
+    }
+}
+
 macro_rules! implement {
     ($($command:ident($position:ident) => $letter:expr,)*) => (
         impl From<Command> for String {
@@ -96,3 +117,17 @@ implement! {
     EllipticalArc(Absolute) => "A",
     EllipticalArc(Relative) => "a",
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Data, Position};
+
+    #[test]
+    fn command_position() {
+        let data = Data::parse("M0 0 l1 1 z").unwrap();
+
+        assert_eq!(data[0].position(), Some(Position::Absolute));
+        assert_eq!(data[1].position(), Some(Position::Relative));
+        assert_eq!(data[2].position(), None);
+    }
+}
@@ -1,5 +1,8 @@
+use std::fmt;
 use std::ops::Deref;
+use std::str::FromStr;
 
+use super::geometry::{parameters_mut, Axis};
 use super::{Command, Number, Parameters, Position};
 use crate::events::parser::{Error, Reader, Result};
 use crate::node::Value;
@@ -21,6 +24,12 @@ impl Data {
         Default::default()
     }
 
+    /// Create a data attribute with space for a number of commands.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Data(Vec::with_capacity(capacity))
+    }
+
     /// Parse a data attribute.
     #[inline]
     pub fn parse(content: &str) -> Result<Self> {
@@ -33,6 +42,175 @@ impl Data {
         self.0.push(command);
         self
     }
+
+    /// Add a `Command::Close` command followed by an absolute `Command::Move`
+    /// command, which starts a new subpath.
+    #[inline]
+    pub fn close_and_move_to<T>(self, parameters: T) -> Self
+    where
+        T: Into<Parameters>,
+    {
+        self.close().move_to(parameters)
+    }
+
+    /// Create a data attribute from commands converted to relative
+    /// positioning.
+    ///
+    /// The resulting path is the same but depends only on where it starts,
+    /// which is convenient for shapes made of many small steps that are
+    /// placed by changing the initial `Command::Move` command alone.
+    #[inline]
+    pub fn relative_path(segments: &[Command]) -> Self {
+        Data(segments.to_vec()).reposition(Position::Relative)
+    }
+
+    /// Convert all commands to absolute positioning.
+    ///
+    /// Relative coordinates are resolved against the current point, which,
+    /// after a `Command::Close` command, is the start of the closed subpath.
+    #[inline]
+    pub fn absolute(&self) -> Self {
+        self.reposition(Position::Absolute)
+    }
+
+    /// Shift the path so that it starts at the origin.
+    ///
+    /// All commands are converted to absolute positioning, and the point of
+    /// the initial `Command::Move` command is subtracted from all
+    /// coordinates. The point is returned too, so that translating by it
+    /// restores the original placement. A path not starting with a
+    /// `Command::Move` command is only converted.
+    pub fn normalized_to_origin(&self) -> (Data, (f64, f64)) {
+        let absolute = self.absolute();
+        let offset = match absolute.0.first() {
+            Some(Command::Move(_, parameters)) if parameters.len() >= 2 => {
+                (parameters[0] as f64, parameters[1] as f64)
+            }
+            _ => return (absolute, (0.0, 0.0)),
+        };
+        let commands = absolute
+            .0
+            .into_iter()
+            .map(|mut command| {
+                let axes = Axis::layout(&command);
+                if let Some(parameters) = parameters_mut(&mut command) {
+                    let values = parameters
+                        .iter()
+                        .zip(axes.iter().cycle())
+                        .map(|(&value, axis)| match axis {
+                            Axis::X => value as f64 - offset.0,
+                            Axis::Y => value as f64 - offset.1,
+                            Axis::None => value as f64,
+                        })
+                        .collect::<Vec<_>>();
+                    *parameters = values.into();
+                }
+                command
+            })
+            .collect();
+        (Data(commands), offset)
+    }
+
+    fn reposition(&self, target: Position) -> Self {
+        use super::Command::*;
+
+        let mut current = (0.0, 0.0);
+        let mut start = (0.0, 0.0);
+        let mut commands = Vec::with_capacity(self.0.len());
+        for command in &self.0 {
+            let (position, parameters) = match command {
+                Move(position, parameters)
+                | Line(position, parameters)
+                | HorizontalLine(position, parameters)
+                | VerticalLine(position, parameters)
+                | QuadraticCurve(position, parameters)
+                | SmoothQuadraticCurve(position, parameters)
+                | CubicCurve(position, parameters)
+                | SmoothCubicCurve(position, parameters)
+                | EllipticalArc(position, parameters) => (position, parameters),
+                Close => {
+                    current = start;
+                    commands.push(Close);
+                    continue;
+                }
+            };
+            let axes = Axis::layout(command);
+            let mut values = Vec::with_capacity(parameters.len());
+            for (i, segment) in parameters.chunks(axes.len()).enumerate() {
+                let mut point = current;
+                for (value, axis) in segment.iter().zip(axes) {
+                    let origin = match axis {
+                        Axis::X => current.0,
+                        Axis::Y => current.1,
+                        Axis::None => 0.0,
+                    };
+                    let value = match position {
+                        Position::Absolute => *value,
+                        Position::Relative => value + origin,
+                    };
+                    match axis {
+                        Axis::X => point.0 = value,
+                        Axis::Y => point.1 = value,
+                        Axis::None => {}
+                    }
+                    values.push(match target {
+                        Position::Absolute => value,
+                        Position::Relative => value - origin,
+                    });
+                }
+                current = point;
+                if i == 0 && matches!(command, Move(..)) {
+                    start = current;
+                }
+            }
+            let parameters = Parameters::from(values);
+            commands.push(match command {
+                Move(..) => Move(target, parameters),
+                Line(..) => Line(target, parameters),
+                HorizontalLine(..) => HorizontalLine(target, parameters),
+                VerticalLine(..) => VerticalLine(target, parameters),
+                QuadraticCurve(..) => QuadraticCurve(target, parameters),
+                SmoothQuadraticCurve(..) => SmoothQuadraticCurve(target, parameters),
+                CubicCurve(..) => CubicCurve(target, parameters),
+                SmoothCubicCurve(..) => SmoothCubicCurve(target, parameters),
+                EllipticalArc(..) => EllipticalArc(target, parameters),
+                Close => unreachable!(),
+            });
+        }
+        Data(commands)
+    }
+}
+
+impl Data {
+    /// Create a smooth curve passing through the given points.
+    ///
+    /// The curve consists of cubic Bézier curves obtained from a Catmull–Rom
+    /// spline, where the end points are repeated to define the tangents at the
+    /// ends. A `tension` of `1` gives the uniform Catmull–Rom spline, smaller
+    /// values give flatter curves, and `0` gives straight lines.
+    pub fn smooth_through(points: &[(f64, f64)], tension: f64) -> Self {
+        let mut data = Data::new();
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(&first), Some(&last)) => (first, last),
+            _ => return data,
+        };
+        data = data.move_to(first);
+        let factor = tension / 6.0;
+        for i in 1..points.len() {
+            let previous = if i > 1 { points[i - 2] } else { first };
+            let (start, end) = (points[i - 1], points[i]);
+            let next = points.get(i + 1).copied().unwrap_or(last);
+            data = data.cubic_curve_to((
+                start.0 + (end.0 - previous.0) * factor,
+                start.1 + (end.1 - previous.1) * factor,
+                end.0 - (next.0 - start.0) * factor,
+                end.1 - (next.1 - start.1) * factor,
+                end.0,
+                end.1,
+            ));
+        }
+        data
+    }
 }
 
 macro_rules! implement {
@@ -46,6 +224,15 @@ macro_rules! implement {
             self
         }
     );
+    (@one #[$doc:meta] fn $method:ident($command:ident, _)) => (
+        #[$doc]
+        pub fn $method<T>(&mut self, position: Position, parameters: T)
+        where
+            T: Into<Parameters>,
+        {
+            self.0.push(Command::$command(position, parameters.into()));
+        }
+    );
     (@one #[$doc:meta] fn $method:ident($command:ident)) => (
         #[$doc]
         pub fn $method(mut self) -> Self {
@@ -117,6 +304,33 @@ implement! {
 
     #[doc = "Add a `Command::Close` command."]
     fn close(Close)
+
+    #[doc = "Add a `Command::Move` command with the given positioning."]
+    fn push_move(Move, _)
+
+    #[doc = "Add a `Command::Line` command with the given positioning."]
+    fn push_line(Line, _)
+
+    #[doc = "Add a `Command::HorizontalLine` command with the given positioning."]
+    fn push_horizontal_line(HorizontalLine, _)
+
+    #[doc = "Add a `Command::VerticalLine` command with the given positioning."]
+    fn push_vertical_line(VerticalLine, _)
+
+    #[doc = "Add a `Command::QuadraticCurve` command with the given positioning."]
+    fn push_quadratic_curve(QuadraticCurve, _)
+
+    #[doc = "Add a `Command::SmoothQuadraticCurve` command with the given positioning."]
+    fn push_smooth_quadratic_curve(SmoothQuadraticCurve, _)
+
+    #[doc = "Add a `Command::CubicCurve` command with the given positioning."]
+    fn push_cubic_curve(CubicCurve, _)
+
+    #[doc = "Add a `Command::SmoothCubicCurve` command with the given positioning."]
+    fn push_smooth_cubic_curve(SmoothCubicCurve, _)
+
+    #[doc = "Add a `Command::EllipticalArc` command with the given positioning."]
+    fn push_elliptical_arc(EllipticalArc, _)
 }
 
 impl Deref for Data {
@@ -128,6 +342,27 @@ impl Deref for Data {
     }
 }
 
+impl fmt::Display for Data {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, command) in self.0.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(" ")?;
+            }
+            formatter.write_str(&String::from(command.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Data {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(content: &str) -> Result<Self> {
+        Data::parse(content)
+    }
+}
+
 impl From<Vec<Command>> for Data {
     #[inline]
     fn from(commands: Vec<Command>) -> Self {
@@ -265,12 +500,9 @@ impl<'l> Parser<'l> {
 
     pub fn read_number(&mut self) -> Result<Option<Number>> {
         self.reader.consume_whitespace();
-        let number = self
-            .reader
-            .capture(|reader| reader.consume_number())
-            .and_then(|number| Some(String::from(number)));
+        let number = self.reader.capture(|reader| reader.consume_number());
         match number {
-            Some(number) => match (&number).parse() {
+            Some(number) => match number.parse() {
                 Ok(number) => Ok(Some(number)),
                 _ => raise!(self, "failed to parse a number '{}'", number),
             },
@@ -281,6 +513,7 @@ impl<'l> Parser<'l> {
 
 #[cfg(test)]
 mod tests {
+    use super::super::geometry::Segment;
     use super::super::Command::*;
     use super::super::Position::*;
     use super::{Data, Parser};
@@ -296,6 +529,152 @@ mod tests {
         assert_eq!(Value::from(data).to_string(), "L1,2 c1,2.5,3,4,5,6 z");
     }
 
+    #[test]
+    fn data_default() {
+        assert_eq!(Data::default().to_string(), "");
+        assert_eq!(Value::from(Data::default()).to_string(), "");
+
+        let data = Data::default().move_to((1, 2)).line_by((3, 4));
+        assert_eq!(data.to_string(), "M1,2 l3,4");
+        assert_eq!(
+            Data::with_capacity(2)
+                .move_to((1, 2))
+                .line_by((3, 4))
+                .to_string(),
+            data.to_string()
+        );
+    }
+
+    #[test]
+    fn data_from_arrays() {
+        let end: [f64; 2] = [3.5, 4.0];
+        let data = Data::new()
+            .move_to([1, 2])
+            .line_to(end)
+            .add(Line(Relative, vec![1.0, 1.0].into()))
+            .cubic_curve_to(vec![1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        assert_eq!(
+            Value::from(data).to_string(),
+            "M1,2 L3.5,4 l1,1 C1,2,3,4,5,6",
+        );
+    }
+
+    #[test]
+    fn data_smooth_through() {
+        let points = [(0.0, 0.0), (10.0, 5.0), (20.0, -5.0), (30.0, 0.0)];
+        let data = Data::smooth_through(&points, 1.0);
+
+        assert_eq!(data.len(), points.len());
+        assert!(matches!(&data[0], Move(Absolute, parameters) if parameters[..] == [0.0, 0.0]));
+        for (command, point) in data[1..].iter().zip(&points[1..]) {
+            match command {
+                CubicCurve(Absolute, parameters) => {
+                    assert_eq!(&parameters[4..], &[point.0 as f32, point.1 as f32]);
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        assert_eq!(
+            Value::from(Data::smooth_through(&points[..3], 1.5)).to_string(),
+            "M0,0 C2.5,1.25,5,6.25,10,5 C15,3.75,17.5,-2.5,20,-5",
+        );
+        assert_eq!(
+            Value::from(Data::smooth_through(&points[..2], 0.0)).to_string(),
+            "M0,0 C0,0,10,5,10,5",
+        );
+        assert!(Data::smooth_through(&[], 1.0).is_empty());
+    }
+
+    #[test]
+    fn data_push() {
+        let table = vec![
+            (Absolute, (10.0, 10.0)),
+            (Relative, (5.0, 0.0)),
+            (Absolute, (15.0, 20.0)),
+        ];
+        let mut data = Data::new().move_to((0, 0));
+        for (position, point) in table {
+            data.push_line(position, point);
+        }
+        data.push_elliptical_arc(Relative, (1, 1, 0, 0, 1, 2, 2));
+
+        assert_eq!(data.to_string(), "M0,0 L10,10 l5,0 L15,20 a1,1,0,0,1,2,2");
+    }
+
+    #[test]
+    fn data_normalized_to_origin() {
+        let data = Data::parse("M50,50 l10,0 V70 A5,5,0,0,1,40,60 z").unwrap();
+        let (normalized, offset) = data.normalized_to_origin();
+
+        assert_eq!(offset, (50.0, 50.0));
+        assert_eq!(normalized.to_string(), "M0,0 L10,0 V20 A5,5,0,0,1,-10,10 z");
+        assert_eq!(Data::new().normalized_to_origin().1, (0.0, 0.0));
+    }
+
+    #[test]
+    fn data_relative_path() {
+        let data = Data::new()
+            .move_to((10, 10))
+            .line_by((5, 0))
+            .cubic_curve_by((2, 0, 5, 3, 5, 5))
+            .line_by((-10, 0))
+            .close()
+            .line_by((0, -5));
+
+        assert_eq!(
+            Value::from(data.absolute()).to_string(),
+            "M10,10 L15,10 C17,10,20,13,20,15 L10,15 z L10,5",
+        );
+        assert_eq!(
+            data.segments()[3],
+            Segment::Line((10.0, 15.0), (10.0, 10.0)),
+        );
+
+        let data = Data::relative_path(&data.absolute());
+        assert_eq!(
+            Value::from(data).to_string(),
+            "m10,10 l5,0 c2,0,5,3,5,5 l-10,0 z l0,-5",
+        );
+    }
+
+    #[test]
+    fn data_parse_large() {
+        let mut data = Data::with_capacity(3 * 5000 + 1).move_to((0, 0));
+        for i in 0..5000 {
+            let x = i as f64 * 0.5;
+            data = data
+                .line_to((x, -x))
+                .cubic_curve_by((1.25, -2, 3, 4e-3, 5, 6))
+                .elliptical_arc_by((1, 2, 30, 1, 0, -x, 0.125));
+        }
+        let content = data.to_string();
+        let parsed = Data::parse(&content).unwrap();
+
+        assert_eq!(parsed.len(), data.len());
+        assert_eq!(parsed.to_string(), content);
+    }
+
+    #[test]
+    fn data_absolute() {
+        let data = Data::parse("M10,10 l5,0 0,5 z m1,1 h2 v2 c1,1 2,2 3,3 a1,1 0 0,1 2,2").unwrap();
+        assert_eq!(
+            Value::from(data.absolute()).to_string(),
+            "M10,10 L15,10,15,15 z M11,11 H13 V13 C14,14,15,15,16,16 A1,1,0,0,1,18,18",
+        );
+
+        let data = Data::new()
+            .move_to((0, 0))
+            .line_by((5, 0))
+            .close_and_move_to((10, 10))
+            .line_by((1, 1));
+        assert_eq!(
+            Value::from(data.absolute()).to_string(),
+            "M0,0 L5,0 z M10,10 L11,11",
+        );
+    }
+
     #[test]
     fn data_parse() {
         let data = Data::parse("M1,2 l3,4").unwrap();
@@ -311,6 +690,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn data_from_str() {
+        let data = "M1,2 l3,4".parse::<Data>().unwrap();
+        assert_eq!(Value::from(data).to_string(), "M1,2 l3,4");
+
+        assert!("M1,2 x".parse::<Data>().is_err());
+    }
+
     #[test]
     fn parser_read_command() {
         macro_rules! run(
@@ -0,0 +1,677 @@
+use std::f64::consts::PI;
+
+use super::{Command, Data, FillRule, Parameters, Position};
+
+/// A point.
+pub(crate) type Point = (f64, f64);
+
+/// An axis a parameter of a command refers to.
+#[derive(Clone, Copy)]
+pub(super) enum Axis {
+    X,
+    Y,
+    None,
+}
+
+/// A tracker of the current point and the start of the current subpath.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Cursor {
+    pub current: Point,
+    pub start: Point,
+}
+
+impl Axis {
+    /// Return the axes of the parameters of one segment of a command.
+    pub(super) fn layout(command: &Command) -> &'static [Axis] {
+        use self::Axis::*;
+        use super::Command::*;
+
+        match command {
+            Move(..) | Line(..) | SmoothQuadraticCurve(..) => &[X, Y],
+            HorizontalLine(..) => &[X],
+            VerticalLine(..) => &[Y],
+            QuadraticCurve(..) | SmoothCubicCurve(..) => &[X, Y, X, Y],
+            CubicCurve(..) => &[X, Y, X, Y, X, Y],
+            EllipticalArc(..) => &[None, None, None, None, None, X, Y],
+            Close => &[],
+        }
+    }
+}
+
+impl Cursor {
+    /// Move past an absolute command.
+    pub fn advance(&mut self, command: &Command) {
+        let parameters = match parameters(command) {
+            Some(parameters) => parameters,
+            _ => {
+                self.current = self.start;
+                return;
+            }
+        };
+        let axes = Axis::layout(command);
+        for (i, segment) in parameters.chunks(axes.len()).enumerate() {
+            for (value, axis) in segment.iter().zip(axes) {
+                match axis {
+                    Axis::X => self.current.0 = *value as f64,
+                    Axis::Y => self.current.1 = *value as f64,
+                    Axis::None => {}
+                }
+            }
+            if i == 0 && matches!(command, Command::Move(..)) {
+                self.start = self.current;
+            }
+        }
+    }
+}
+
+impl Data {
+    /// Replace elliptical arcs with cubic Bézier curves.
+    ///
+    /// Each arc is split into pieces spanning at most 90 degrees, and each
+    /// piece is approximated by one cubic curve. An arc keeps its positioning,
+    /// and all other commands are left intact, except for a smooth curve
+    /// following an arc, whose first segment is made explicit so that it does
+    /// not reflect the control point of the new cubic curve. Parameters that
+    /// do not make up a whole arc are kept as an arc.
+    pub fn arcs_to_cubics(&self) -> Data {
+        let mut cursor = Cursor::default();
+        let mut commands = Vec::with_capacity(self.len());
+        let mut converted = false;
+        for (command, absolute) in self.iter().zip(self.absolute().iter()) {
+            if let Command::EllipticalArc(position, original) = command {
+                let whole = original.len() / 7 * 7;
+                let mut values = Vec::new();
+                for segment in parameters(absolute).unwrap()[..whole].chunks(7) {
+                    let segment = segment
+                        .iter()
+                        .map(|&value| value as f64)
+                        .collect::<Vec<_>>();
+                    let from = cursor.current;
+                    let to = (segment[5], segment[6]);
+                    let arc = Arc {
+                        radii: (segment[0], segment[1]),
+                        rotation: segment[2],
+                        large_arc: segment[3] != 0.0,
+                        sweep: segment[4] != 0.0,
+                    };
+                    let mut start = from;
+                    for [x1, y1, x2, y2, x, y] in arc.to_cubics(from, to) {
+                        let offset = match position {
+                            Position::Absolute => (0.0, 0.0),
+                            Position::Relative => start,
+                        };
+                        values.extend_from_slice(&[
+                            x1 - offset.0,
+                            y1 - offset.1,
+                            x2 - offset.0,
+                            y2 - offset.1,
+                            x - offset.0,
+                            y - offset.1,
+                        ]);
+                        start = (x, y);
+                    }
+                    cursor.current = to;
+                }
+                if !values.is_empty() {
+                    let values = values
+                        .into_iter()
+                        .map(|value| value as f32)
+                        .collect::<Vec<_>>();
+                    commands.push(Command::CubicCurve(*position, Parameters::from(values)));
+                    converted = true;
+                }
+                if whole < original.len() {
+                    let rest = Parameters::from(original[whole..].to_vec());
+                    commands.push(Command::EllipticalArc(*position, rest));
+                    converted = false;
+                }
+                continue;
+            }
+            match command {
+                Command::SmoothCubicCurve(position, parameters)
+                    if converted && parameters.len() >= 4 =>
+                {
+                    let (first, rest) = make_explicit(*position, parameters, 4, cursor.current);
+                    commands.push(Command::CubicCurve(*position, first));
+                    if let Some(rest) = rest {
+                        commands.push(Command::SmoothCubicCurve(*position, rest));
+                    }
+                }
+                Command::SmoothQuadraticCurve(position, parameters)
+                    if converted && parameters.len() >= 2 =>
+                {
+                    let (first, rest) = make_explicit(*position, parameters, 2, cursor.current);
+                    commands.push(Command::QuadraticCurve(*position, first));
+                    if let Some(rest) = rest {
+                        commands.push(Command::SmoothQuadraticCurve(*position, rest));
+                    }
+                }
+                _ => commands.push(command.clone()),
+            }
+            cursor.advance(absolute);
+            converted = false;
+        }
+        commands.into()
+    }
+}
+
+// Split the first segment off the parameters of a smooth curve and prepend
+// the implicit control point, which is the current point.
+fn make_explicit(
+    position: Position,
+    parameters: &Parameters,
+    length: usize,
+    current: Point,
+) -> (Parameters, Option<Parameters>) {
+    let control = match position {
+        Position::Absolute => current,
+        Position::Relative => (0.0, 0.0),
+    };
+    let mut first = vec![control.0 as f32, control.1 as f32];
+    first.extend_from_slice(&parameters[..length]);
+    let rest = match parameters.len() > length {
+        true => Some(Parameters::from(parameters[length..].to_vec())),
+        false => None,
+    };
+    (first.into(), rest)
+}
+
+impl Data {
+    /// Compute the total length.
+    ///
+    /// Curves are measured by subdividing them into short straight pieces,
+    /// which makes the result approximate for anything but lines.
+    pub fn length(&self) -> f64 {
+        self.segments().iter().map(Segment::length).sum()
+    }
+
+    /// Find the point lying at a distance along the path from its start.
+    ///
+    /// `None` is returned if the distance is negative or exceeds the total
+    /// length.
+    pub fn point_at_length(&self, length: f64) -> Option<Point> {
+        if length < 0.0 {
+            return None;
+        }
+        let mut remaining = length;
+        for segment in self.segments() {
+            let points = segment.flatten();
+            for pair in points.windows(2) {
+                let distance = distance(pair[0], pair[1]);
+                if remaining <= distance {
+                    let t = if distance > 0.0 {
+                        remaining / distance
+                    } else {
+                        0.0
+                    };
+                    return Some((
+                        pair[0].0 + t * (pair[1].0 - pair[0].0),
+                        pair[0].1 + t * (pair[1].1 - pair[0].1),
+                    ));
+                }
+                remaining -= distance;
+            }
+        }
+        None
+    }
+
+    /// Compute the bounding box as `(x, y, width, height)`.
+    ///
+    /// Curves are approximated with straight pieces, and the stroke is not
+    /// taken into account. `None` is returned if nothing is drawn.
+    pub fn bounding_box(&self) -> Option<(f64, f64, f64, f64)> {
+        let mut points = self
+            .segments()
+            .into_iter()
+            .flat_map(|segment| segment.flatten());
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), point| {
+            (
+                (min.0.min(point.0), min.1.min(point.1)),
+                (max.0.max(point.0), max.1.max(point.1)),
+            )
+        });
+        Some((min.0, min.1, max.0 - min.0, max.1 - min.1))
+    }
+
+    /// Check if a point lies in the area filled according to a fill rule.
+    ///
+    /// Open subpaths are treated as closed, as they are when filled, and
+    /// curves are approximated with straight pieces.
+    pub fn contains_point(&self, (x, y): Point, fill_rule: FillRule) -> bool {
+        let mut winding = 0;
+        for subpath in self.subpaths() {
+            let points = subpath
+                .iter()
+                .flat_map(Segment::flatten)
+                .collect::<Vec<_>>();
+            let closing = points.last().copied().zip(points.first().copied());
+            let edges = points.windows(2).map(|pair| (pair[0], pair[1]));
+            for (a, b) in edges.chain(closing) {
+                let side = (b.0 - a.0) * (y - a.1) - (x - a.0) * (b.1 - a.1);
+                if a.1 <= y && y < b.1 && side > 0.0 {
+                    winding += 1;
+                } else if b.1 <= y && y < a.1 && side < 0.0 {
+                    winding -= 1;
+                }
+            }
+        }
+        match fill_rule {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Return the drawn segments, with quadratic curves, smooth curves, and
+    /// arcs converted into cubic curves.
+    #[inline]
+    pub(crate) fn segments(&self) -> Vec<Segment> {
+        self.subpaths().concat()
+    }
+
+    /// Return the drawn segments grouped by subpath.
+    pub(crate) fn subpaths(&self) -> Vec<Vec<Segment>> {
+        use super::Command::*;
+
+        let mut subpaths = Vec::new();
+        let mut segments = Vec::new();
+        let mut cursor = Cursor::default();
+        let mut control = None;
+        for command in self.absolute().iter() {
+            let mut previous = control.take();
+            let parameters = match parameters(command) {
+                Some(parameters) => parameters
+                    .iter()
+                    .map(|&value| value as f64)
+                    .collect::<Vec<_>>(),
+                _ => {
+                    segments.push(Segment::Line(cursor.current, cursor.start));
+                    cursor.current = cursor.start;
+                    continue;
+                }
+            };
+            let chunk = Axis::layout(command).len();
+            for (i, p) in parameters.chunks(chunk).enumerate() {
+                if p.len() < chunk {
+                    break;
+                }
+                let from = cursor.current;
+                let (segment, to) = match command {
+                    Move(..) if i == 0 => {
+                        if !segments.is_empty() {
+                            subpaths.push(std::mem::take(&mut segments));
+                        }
+                        cursor.start = (p[0], p[1]);
+                        (None, (p[0], p[1]))
+                    }
+                    Move(..) | Line(..) => (Some(Segment::Line(from, (p[0], p[1]))), (p[0], p[1])),
+                    HorizontalLine(..) => {
+                        (Some(Segment::Line(from, (p[0], from.1))), (p[0], from.1))
+                    }
+                    VerticalLine(..) => (Some(Segment::Line(from, (from.0, p[0]))), (from.0, p[0])),
+                    QuadraticCurve(..) | SmoothQuadraticCurve(..) => {
+                        let (q, to) = match command {
+                            QuadraticCurve(..) => ((p[0], p[1]), (p[2], p[3])),
+                            _ => match previous {
+                                Some(Control::Quadratic(q)) => (reflect(q, from), (p[0], p[1])),
+                                _ => (from, (p[0], p[1])),
+                            },
+                        };
+                        previous = Some(Control::Quadratic(q));
+                        let c1 = (
+                            from.0 + 2.0 / 3.0 * (q.0 - from.0),
+                            from.1 + 2.0 / 3.0 * (q.1 - from.1),
+                        );
+                        let c2 = (
+                            to.0 + 2.0 / 3.0 * (q.0 - to.0),
+                            to.1 + 2.0 / 3.0 * (q.1 - to.1),
+                        );
+                        (Some(Segment::Cubic([from, c1, c2, to])), to)
+                    }
+                    CubicCurve(..) | SmoothCubicCurve(..) => {
+                        let (c1, c2, to) = match command {
+                            CubicCurve(..) => ((p[0], p[1]), (p[2], p[3]), (p[4], p[5])),
+                            _ => match previous {
+                                Some(Control::Cubic(c)) => {
+                                    (reflect(c, from), (p[0], p[1]), (p[2], p[3]))
+                                }
+                                _ => (from, (p[0], p[1]), (p[2], p[3])),
+                            },
+                        };
+                        previous = Some(Control::Cubic(c2));
+                        (Some(Segment::Cubic([from, c1, c2, to])), to)
+                    }
+                    EllipticalArc(..) => {
+                        let to = (p[5], p[6]);
+                        let arc = Arc {
+                            radii: (p[0], p[1]),
+                            rotation: p[2],
+                            large_arc: p[3] != 0.0,
+                            sweep: p[4] != 0.0,
+                        };
+                        let mut start = from;
+                        for [x1, y1, x2, y2, x, y] in arc.to_cubics(from, to) {
+                            segments.push(Segment::Cubic([start, (x1, y1), (x2, y2), (x, y)]));
+                            start = (x, y);
+                        }
+                        (None, to)
+                    }
+                    Close => unreachable!(),
+                };
+                segments.extend(segment);
+                cursor.current = to;
+            }
+            control = match command {
+                QuadraticCurve(..)
+                | SmoothQuadraticCurve(..)
+                | CubicCurve(..)
+                | SmoothCubicCurve(..) => previous,
+                _ => None,
+            };
+        }
+        if !segments.is_empty() {
+            subpaths.push(segments);
+        }
+        subpaths
+    }
+}
+
+/// A drawn segment of a path.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Segment {
+    Line(Point, Point),
+    Cubic([Point; 4]),
+}
+
+/// The last control point of a curve, which smooth curves reflect.
+#[derive(Clone, Copy)]
+enum Control {
+    Quadratic(Point),
+    Cubic(Point),
+}
+
+impl Segment {
+    /// The number of straight pieces a curve is subdivided into for measuring.
+    const PIECES: usize = 64;
+
+    /// Compute the length.
+    pub fn length(&self) -> f64 {
+        self.flatten()
+            .windows(2)
+            .map(|pair| distance(pair[0], pair[1]))
+            .sum()
+    }
+
+    /// Approximate the segment with a polyline.
+    pub fn flatten(&self) -> Vec<Point> {
+        match *self {
+            Segment::Line(from, to) => vec![from, to],
+            Segment::Cubic([p0, p1, p2, p3]) => (0..=Self::PIECES)
+                .map(|i| {
+                    let t = i as f64 / Self::PIECES as f64;
+                    let u = 1.0 - t;
+                    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+                    (
+                        a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+                        a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// An elliptical arc in the endpoint parameterization.
+pub(crate) struct Arc {
+    pub radii: (f64, f64),
+    pub rotation: f64,
+    pub large_arc: bool,
+    pub sweep: bool,
+}
+
+impl Arc {
+    /// Approximate the arc with cubic Bézier curves.
+    ///
+    /// Each curve is given by its two control points and its endpoint.
+    // https://www.w3.org/TR/SVG/implnote.html#ArcImplementationNotes
+    pub fn to_cubics(&self, from: Point, to: Point) -> Vec<[f64; 6]> {
+        if from == to {
+            return Vec::new();
+        }
+        let (mut rx, mut ry) = (self.radii.0.abs(), self.radii.1.abs());
+        if rx == 0.0 || ry == 0.0 {
+            return vec![[
+                from.0 + (to.0 - from.0) / 3.0,
+                from.1 + (to.1 - from.1) / 3.0,
+                from.0 + 2.0 * (to.0 - from.0) / 3.0,
+                from.1 + 2.0 * (to.1 - from.1) / 3.0,
+                to.0,
+                to.1,
+            ]];
+        }
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (dx, dy) = ((from.0 - to.0) / 2.0, (from.1 - to.1) / 2.0);
+        let (x1, y1) = (cos * dx + sin * dy, -sin * dx + cos * dy);
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coefficient = (numerator / denominator).max(0.0).sqrt();
+        if self.large_arc == self.sweep {
+            coefficient = -coefficient;
+        }
+        let (cx1, cy1) = (coefficient * rx * y1 / ry, -coefficient * ry * x1 / rx);
+        let center = (
+            cos * cx1 - sin * cy1 + (from.0 + to.0) / 2.0,
+            sin * cx1 + cos * cy1 + (from.1 + to.1) / 2.0,
+        );
+        let start = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+        let end = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+        let mut sweep = end - start;
+        if self.sweep && sweep < 0.0 {
+            sweep += 2.0 * PI;
+        } else if !self.sweep && sweep > 0.0 {
+            sweep -= 2.0 * PI;
+        }
+
+        let count = (sweep.abs() / (PI / 2.0) - 1e-9).ceil().max(1.0) as usize;
+        let step = sweep / count as f64;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let map = |(x, y): Point| {
+            (
+                center.0 + rx * cos * x - ry * sin * y,
+                center.1 + rx * sin * x + ry * cos * y,
+            )
+        };
+        (0..count)
+            .map(|i| {
+                let (a, b) = (start + i as f64 * step, start + (i + 1) as f64 * step);
+                let (c1, c2) = (
+                    map((a.cos() - k * a.sin(), a.sin() + k * a.cos())),
+                    map((b.cos() + k * b.sin(), b.sin() - k * b.cos())),
+                );
+                let end = if i + 1 == count {
+                    to
+                } else {
+                    map((b.cos(), b.sin()))
+                };
+                [c1.0, c1.1, c2.0, c2.1, end.0, end.1]
+            })
+            .collect()
+    }
+}
+
+fn distance(a: Point, b: Point) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn reflect(point: Point, center: Point) -> Point {
+    (2.0 * center.0 - point.0, 2.0 * center.1 - point.1)
+}
+
+pub(crate) fn parameters(command: &Command) -> Option<&Parameters> {
+    use super::Command::*;
+
+    match command {
+        Move(_, parameters)
+        | Line(_, parameters)
+        | HorizontalLine(_, parameters)
+        | VerticalLine(_, parameters)
+        | QuadraticCurve(_, parameters)
+        | SmoothQuadraticCurve(_, parameters)
+        | CubicCurve(_, parameters)
+        | SmoothCubicCurve(_, parameters)
+        | EllipticalArc(_, parameters) => Some(parameters),
+        Close => None,
+    }
+}
+
+pub(super) fn parameters_mut(command: &mut Command) -> Option<&mut Parameters> {
+    use super::Command::*;
+
+    match command {
+        Move(_, parameters)
+        | Line(_, parameters)
+        | HorizontalLine(_, parameters)
+        | VerticalLine(_, parameters)
+        | QuadraticCurve(_, parameters)
+        | SmoothQuadraticCurve(_, parameters)
+        | CubicCurve(_, parameters)
+        | SmoothCubicCurve(_, parameters)
+        | EllipticalArc(_, parameters) => Some(parameters),
+        Close => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Command, Data, FillRule, Position};
+
+    #[test]
+    fn data_arcs_to_cubics() {
+        let data = Data::parse("M0,0 A50,50 0 0,1 100,0 L100,10").unwrap();
+        let data = data.arcs_to_cubics();
+
+        assert_eq!(data.len(), 3);
+        assert!(matches!(data[2], Command::Line(Position::Absolute, _)));
+        match &data[1] {
+            Command::CubicCurve(Position::Absolute, parameters) => {
+                assert_eq!(parameters.len(), 12);
+                let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+                assert!(close(parameters[4], 50.0) && close(parameters[5], -50.0));
+                assert!(close(parameters[10], 100.0) && close(parameters[11], 0.0));
+            }
+            _ => unreachable!(),
+        }
+
+        let data = Data::parse("M10,10 a50,50 0 0,1 100,0").unwrap();
+        match &data.arcs_to_cubics()[1] {
+            Command::CubicCurve(Position::Relative, parameters) => {
+                let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+                assert!(close(parameters[4], 50.0) && close(parameters[5], -50.0));
+                assert!(close(parameters[10], 50.0) && close(parameters[11], 50.0));
+            }
+            _ => unreachable!(),
+        }
+
+        let data = Data::parse("M0,0 A5,5 0 0 1 10,0 S20,10 30,0 40,10 50,0").unwrap();
+        let data = data.arcs_to_cubics();
+        assert_eq!(data.len(), 4);
+        assert!(matches!(
+            &data[2],
+            Command::CubicCurve(Position::Absolute, parameters)
+                if parameters[..] == [10.0, 0.0, 20.0, 10.0, 30.0, 0.0]
+        ));
+        assert!(matches!(
+            &data[3],
+            Command::SmoothCubicCurve(Position::Absolute, parameters)
+                if parameters[..] == [40.0, 10.0, 50.0, 0.0]
+        ));
+
+        let data = Data::parse("M0,0 a5,5 0 0 1 10,0 t10,10").unwrap();
+        assert!(matches!(
+            &data.arcs_to_cubics()[2],
+            Command::QuadraticCurve(Position::Relative, parameters)
+                if parameters[..] == [0.0, 0.0, 10.0, 10.0]
+        ));
+
+        let data = Data::parse("M0,0 A5,5 0 0 1 L10,10").unwrap();
+        let data = data.arcs_to_cubics();
+        assert_eq!(data.len(), 3);
+        assert!(matches!(
+            &data[1],
+            Command::EllipticalArc(Position::Absolute, parameters)
+                if parameters[..] == [5.0, 5.0, 0.0, 0.0, 1.0]
+        ));
+        assert!(matches!(
+            &data[2],
+            Command::Line(Position::Absolute, parameters) if parameters[..] == [10.0, 10.0]
+        ));
+
+        let data = Data::parse("M0,0 a5,5 0 0 1 10,0 20 s10,10 20,0").unwrap();
+        let data = data.arcs_to_cubics();
+        assert_eq!(data.len(), 4);
+        assert!(matches!(
+            &data[1],
+            Command::CubicCurve(Position::Relative, _)
+        ));
+        assert!(matches!(
+            &data[2],
+            Command::EllipticalArc(Position::Relative, parameters) if parameters[..] == [20.0]
+        ));
+        assert!(matches!(
+            &data[3],
+            Command::SmoothCubicCurve(Position::Relative, _)
+        ));
+    }
+
+    #[test]
+    fn data_length() {
+        let data = Data::parse("M0,0 L100,0").unwrap();
+        assert_eq!(data.length(), 100.0);
+        assert_eq!(data.point_at_length(50.0), Some((50.0, 0.0)));
+        assert_eq!(data.point_at_length(100.0), Some((100.0, 0.0)));
+        assert_eq!(data.point_at_length(101.0), None);
+
+        let data = Data::parse("M0,0 h10 v10 z").unwrap();
+        assert!((data.length() - (20.0 + 200f64.sqrt())).abs() < 1e-9);
+        assert_eq!(data.point_at_length(15.0), Some((10.0, 5.0)));
+
+        let data = Data::parse("M0,0 A50,50 0 0,1 100,0").unwrap();
+        assert!((data.length() - 50.0 * std::f64::consts::PI).abs() < 1e-1);
+    }
+
+    #[test]
+    fn data_bounding_box() {
+        let data = Data::parse("M10,10 h20 v-5 L0,20").unwrap();
+        assert_eq!(data.bounding_box(), Some((0.0, 5.0, 30.0, 15.0)));
+
+        let data = Data::parse("M0,0 C0,-10 10,-10 10,0").unwrap();
+        let (_, y, _, height) = data.bounding_box().unwrap();
+        assert!((y + 7.5).abs() < 1e-9 && (height - 7.5).abs() < 1e-9);
+
+        assert_eq!(Data::parse("M10,10").unwrap().bounding_box(), None);
+    }
+
+    #[test]
+    fn data_contains_point() {
+        let data = Data::parse("M0,0 h10 v10 h-10").unwrap();
+        assert!(data.contains_point((5.0, 5.0), FillRule::NonZero));
+        assert!(!data.contains_point((15.0, 5.0), FillRule::NonZero));
+        assert!(!data.contains_point((5.0, -1.0), FillRule::EvenOdd));
+
+        let data = Data::parse(
+            "M0,50 a50,50 0 1,0 100,0 a50,50 0 1,0 -100,0 z \
+             M25,50 a25,25 0 1,0 50,0 a25,25 0 1,0 -50,0 z",
+        )
+        .unwrap();
+        assert!(data.contains_point((10.0, 50.0), FillRule::EvenOdd));
+        assert!(data.contains_point((10.0, 50.0), FillRule::NonZero));
+        assert!(!data.contains_point((50.0, 50.0), FillRule::EvenOdd));
+        assert!(data.contains_point((50.0, 50.0), FillRule::NonZero));
+        assert!(!data.contains_point((95.0, 95.0), FillRule::NonZero));
+    }
+}
@@ -0,0 +1,118 @@
+use std::mem;
+use std::ops::Deref;
+
+use super::geometry::{parameters, parameters_mut};
+use super::{Command, Data};
+
+/// The difference between the parameters of two commands of the same kind.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommandDelta(Vec<f64>);
+
+impl Deref for CommandDelta {
+    type Target = [f64];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Data {
+    /// Compute the differences between the parameters of this path and those
+    /// of another one, command by command.
+    ///
+    /// The result is `None` unless the two paths have the same structure,
+    /// that is, the same commands with the same positioning and numbers of
+    /// parameters in the same order. The flags of elliptical arcs have to be
+    /// equal too, as they cannot be interpolated.
+    pub fn diff(&self, other: &Data) -> Option<Vec<CommandDelta>> {
+        if self.len() != other.len() {
+            return None;
+        }
+        self.iter()
+            .zip(other.iter())
+            .map(|(from, to)| delta(from, to))
+            .collect()
+    }
+
+    /// Create a path between this one and another one with the same
+    /// structure, where `t` equal to zero gives this path and `t` equal to
+    /// one gives the other one.
+    ///
+    /// The result is `None` if the structures differ; see `diff`.
+    pub fn interpolate(&self, other: &Data, t: f64) -> Option<Data> {
+        let deltas = self.diff(other)?;
+        let commands = self
+            .iter()
+            .zip(deltas)
+            .map(|(command, CommandDelta(delta))| {
+                let mut command = command.clone();
+                if let Some(parameters) = parameters_mut(&mut command) {
+                    let values = parameters
+                        .iter()
+                        .zip(delta)
+                        .map(|(&value, delta)| value as f64 + t * delta)
+                        .collect::<Vec<_>>();
+                    *parameters = values.into();
+                }
+                command
+            })
+            .collect::<Vec<_>>();
+        Some(commands.into())
+    }
+}
+
+fn delta(from: &Command, to: &Command) -> Option<CommandDelta> {
+    if mem::discriminant(from) != mem::discriminant(to) || from.position() != to.position() {
+        return None;
+    }
+    let (from_parameters, to_parameters) = match (parameters(from), parameters(to)) {
+        (Some(from), Some(to)) => (from, to),
+        _ => return Some(CommandDelta(Vec::new())),
+    };
+    if from_parameters.len() != to_parameters.len() {
+        return None;
+    }
+    let arc = matches!(from, Command::EllipticalArc(..));
+    let mut delta = Vec::with_capacity(from_parameters.len());
+    for (i, (&from, &to)) in from_parameters.iter().zip(to_parameters.iter()).enumerate() {
+        if arc && (i % 7 == 3 || i % 7 == 4) && from != to {
+            return None;
+        }
+        delta.push(to as f64 - from as f64);
+    }
+    Some(CommandDelta(delta))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Data;
+
+    #[test]
+    fn data_interpolate() {
+        let from = Data::parse("M0,0 L10,0 L0,10 z").unwrap();
+        let to = Data::parse("M2,2 L30,2 L2,20 z").unwrap();
+
+        let deltas = from.diff(&to).unwrap();
+        assert_eq!(deltas.len(), 4);
+        assert_eq!(&*deltas[1], &[20.0, 2.0]);
+        assert!(deltas[3].is_empty());
+
+        let middle = from.interpolate(&to, 0.5).unwrap();
+        assert_eq!(middle.to_string(), "M1,1 L20,1 L1,15 z");
+        assert_eq!(
+            from.interpolate(&to, 1.0).unwrap().to_string(),
+            to.to_string()
+        );
+
+        let other = Data::parse("M0,0 l10,0 L0,10 z").unwrap();
+        assert!(from.diff(&other).is_none());
+        assert!(from.interpolate(&other, 0.5).is_none());
+        let other = Data::parse("M0,0 L10,0 z").unwrap();
+        assert!(from.diff(&other).is_none());
+
+        let from = Data::parse("M0,0 A5,5,0,0,1,10,0").unwrap();
+        let to = Data::parse("M0,0 A5,5,0,1,1,10,0").unwrap();
+        assert!(from.diff(&to).is_none());
+    }
+}
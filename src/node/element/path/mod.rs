@@ -2,15 +2,47 @@
 
 mod command;
 mod data;
+mod geometry;
+mod interpolate;
 mod parameters;
+mod shapes;
 
 pub use self::command::Command;
 pub use self::data::Data;
+pub use self::interpolate::CommandDelta;
 pub use self::parameters::Parameters;
 
+pub(crate) use self::geometry::parameters;
+
 /// A number.
 pub type Number = f32;
 
+/// A [fill rule](https://www.w3.org/TR/SVG/painting.html#FillRuleProperty).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum FillRule {
+    /// `nonzero`.
+    #[default]
+    NonZero,
+    /// `evenodd`.
+    EvenOdd,
+}
+
+impl std::fmt::Display for FillRule {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str(match self {
+            FillRule::NonZero => "nonzero",
+            FillRule::EvenOdd => "evenodd",
+        })
+    }
+}
+
+impl From<FillRule> for crate::node::Value {
+    #[inline]
+    fn from(fill_rule: FillRule) -> Self {
+        fill_rule.to_string().into()
+    }
+}
+
 /// A positioning method.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Position {
@@ -32,6 +32,26 @@ impl From<Vec<Number>> for Parameters {
     }
 }
 
+impl From<Vec<f64>> for Parameters {
+    #[inline]
+    fn from(inner: Vec<f64>) -> Self {
+        Parameters(inner.into_iter().map(|value| value as Number).collect())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Parameters
+where
+    T: Into<Parameters>,
+{
+    fn from(inner: [T; N]) -> Self {
+        let mut result = vec![];
+        for value in inner {
+            result.append(&mut value.into().into());
+        }
+        Parameters(result)
+    }
+}
+
 impl From<Parameters> for Vec<Number> {
     #[inline]
     fn from(Parameters(inner): Parameters) -> Self {
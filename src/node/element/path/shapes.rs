@@ -0,0 +1,189 @@
+use std::iter::FromIterator;
+
+use super::Data;
+
+impl Data {
+    /// Create a polyline passing through the given points.
+    ///
+    /// The polyline consists of a `Command::Move` command followed by
+    /// `Command::Line` commands and, if it is closed, a `Command::Close`
+    /// command. No points yield no commands.
+    #[inline]
+    pub fn polyline(points: &[(f64, f64)], closed: bool) -> Self {
+        polyline(points.iter().copied(), closed)
+    }
+
+    /// Create a grid of vertical and horizontal lines.
+    ///
+    /// Lines are placed at every step starting from the lower end of each
+    /// range and up to its upper end inclusively. Each line is a separate
+    /// subpath consisting of a `Command::Move` command followed by a
+    /// `Command::VerticalLine` or `Command::HorizontalLine` command. A step
+    /// that is not positive yields no lines in its direction.
+    pub fn grid(x_range: (f64, f64), y_range: (f64, f64), x_step: f64, y_step: f64) -> Self {
+        let mut data = Data::new();
+        for x in steps(x_range, x_step) {
+            data = data.move_to((x, y_range.0)).vertical_line_to(y_range.1);
+        }
+        for y in steps(y_range, y_step) {
+            data = data.move_to((x_range.0, y)).horizontal_line_to(x_range.1);
+        }
+        data
+    }
+
+    /// Create a closed regular polygon.
+    ///
+    /// The first vertex is placed straight above the center, that is, in the
+    /// direction of the negative y axis, and the rest follow clockwise as
+    /// displayed. Fewer than three sides yield no commands.
+    pub fn regular_polygon(center: (f64, f64), radius: f64, sides: usize) -> Self {
+        if sides < 3 {
+            return Data::new();
+        }
+        polyline((0..sides).map(|i| vertex(center, radius, i, sides)), true)
+    }
+
+    /// Create a closed star.
+    ///
+    /// Outer and inner vertices alternate, starting with an outer vertex placed
+    /// straight above the center as in `Data::regular_polygon`. Fewer than two
+    /// points yield no commands.
+    pub fn star(center: (f64, f64), outer_radius: f64, inner_radius: f64, points: usize) -> Self {
+        if points < 2 {
+            return Data::new();
+        }
+        polyline(
+            (0..2 * points).map(|i| {
+                let radius = if i % 2 == 0 {
+                    outer_radius
+                } else {
+                    inner_radius
+                };
+                vertex(center, radius, i, 2 * points)
+            }),
+            true,
+        )
+    }
+}
+
+impl FromIterator<(f64, f64)> for Data {
+    /// Create an open polyline as `Data::polyline` does.
+    #[inline]
+    fn from_iter<T>(points: T) -> Self
+    where
+        T: IntoIterator<Item = (f64, f64)>,
+    {
+        polyline(points.into_iter(), false)
+    }
+}
+
+fn polyline<T>(mut vertices: T, closed: bool) -> Data
+where
+    T: Iterator<Item = (f64, f64)>,
+{
+    let mut data = match vertices.next() {
+        Some(vertex) => Data::new().move_to(vertex),
+        _ => return Data::new(),
+    };
+    for vertex in vertices {
+        data = data.line_to(vertex);
+    }
+    if closed {
+        data.close()
+    } else {
+        data
+    }
+}
+
+fn vertex((x, y): (f64, f64), radius: f64, i: usize, count: usize) -> (f64, f64) {
+    use std::f64::consts::PI;
+
+    let angle = -PI / 2.0 + 2.0 * PI * i as f64 / count as f64;
+    (x + radius * angle.cos(), y + radius * angle.sin())
+}
+
+fn steps((start, end): (f64, f64), step: f64) -> impl Iterator<Item = f64> {
+    let count = if step > 0.0 && end >= start {
+        ((end - start) / step + 1e-9).floor() as usize + 1
+    } else {
+        0
+    };
+    (0..count).map(move |i| start + i as f64 * step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Command, Data};
+    use crate::node::Value;
+
+    #[test]
+    fn data_polyline() {
+        let points = [(0.0, 0.0), (10.0, 0.0), (5.0, 8.0)];
+        let data = Data::polyline(&points, true);
+
+        assert_eq!(data.len(), 4);
+        assert!(matches!(data.last(), Some(Command::Close)));
+        assert_eq!(Value::from(data).to_string(), "M0,0 L10,0 L5,8 z");
+
+        let data = points.iter().copied().collect::<Data>();
+        assert_eq!(Value::from(data).to_string(), "M0,0 L10,0 L5,8");
+        assert!(Data::polyline(&[], true).is_empty());
+    }
+
+    #[test]
+    fn data_grid() {
+        let data = Data::grid((0.0, 2.0), (0.0, 2.0), 1.0, 1.0);
+
+        let count =
+            |check: fn(&Command) -> bool| data.iter().filter(|command| check(command)).count();
+        assert_eq!(count(|command| matches!(command, Command::Move(..))), 6);
+        assert_eq!(
+            count(|command| matches!(command, Command::VerticalLine(..))),
+            3
+        );
+        assert_eq!(
+            count(|command| matches!(command, Command::HorizontalLine(..))),
+            3
+        );
+        assert_eq!(
+            Value::from(Data::grid((0.0, 1.0), (0.0, 2.0), 1.0, 2.0)).to_string(),
+            "M0,0 V2 M1,0 V2 M0,0 H1 M0,2 H1",
+        );
+        assert!(Data::grid((0.0, 1.0), (0.0, 1.0), 0.0, -1.0).is_empty());
+    }
+
+    #[test]
+    fn data_regular_polygon() {
+        let data = Data::regular_polygon((0.0, 0.0), 2.0, 3);
+
+        assert_eq!(data.len(), 4);
+        let mut vertices = Vec::new();
+        for command in data.iter() {
+            match command {
+                Command::Move(_, parameters) | Command::Line(_, parameters) => {
+                    vertices.push((parameters[0], parameters[1]))
+                }
+                Command::Close => {}
+                _ => unreachable!(),
+            }
+        }
+        assert_eq!(vertices.len(), 3);
+        assert!(matches!(data[3], Command::Close));
+        assert!((vertices[0].0 - 0.0).abs() < 1e-6 && (vertices[0].1 + 2.0).abs() < 1e-6);
+        assert!((vertices[1].0 - 3f32.sqrt()).abs() < 1e-6 && (vertices[1].1 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn data_star() {
+        let data = Data::star((10.0, 10.0), 5.0, 2.0, 5);
+
+        assert_eq!(data.len(), 11);
+        match &data[5] {
+            Command::Line(_, parameters) => {
+                assert!((parameters[0] - 10.0).abs() < 1e-5);
+                assert!((parameters[1] - 12.0).abs() < 1e-5);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
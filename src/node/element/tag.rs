@@ -3,7 +3,8 @@
 #![allow(non_upper_case_globals)]
 
 use crate::events::parser::{Error, Reader, Result};
-use crate::node::Attributes;
+use crate::node::{Attributes, Quote, Value};
+use crate::xml;
 
 /// A tag.
 #[derive(Clone, Debug)]
@@ -24,30 +25,82 @@ pub enum Type {
 
 struct Parser<'l> {
     reader: Reader<'l>,
+    lenient: bool,
+    spacing: bool,
+    max_attributes: Option<usize>,
 }
 
+macro_rules! raise(
+    ($parser:expr, $($argument:tt)*) => (
+        return Err(
+            Error::new($parser.reader.position(), format!($($argument)*))
+                .with_offset($parser.reader.byte_offset())
+        );
+    );
+);
+
 impl<'l> Tag<'l> {
     /// Parse a tag.
     #[inline]
     pub fn parse(content: &'l str) -> Result<Tag<'l>> {
         Parser::new(content).process()
     }
-}
 
-macro_rules! raise(
-    ($parser:expr, $($argument:tt)*) => (
-        return Err(Error::new($parser.reader.position(), format!($($argument)*)));
-    );
-);
+    /// Parse a tag allowing attribute values without quotes.
+    ///
+    /// An unquoted value extends up to the next whitespace character or the
+    /// end of the tag.
+    #[inline]
+    pub fn parse_lenient(content: &'l str) -> Result<Tag<'l>> {
+        Tag::parse_with(content, true, false, None)
+    }
+
+    pub(crate) fn parse_with(
+        content: &'l str,
+        lenient: bool,
+        spacing: bool,
+        max_attributes: Option<usize>,
+    ) -> Result<Tag<'l>> {
+        let new = |content| Parser {
+            spacing,
+            max_attributes,
+            ..Parser::new(content)
+        };
+        if !lenient {
+            return new(content).process();
+        }
+        match content.strip_suffix('/') {
+            Some(content) => {
+                let mut parser = new(content).lenient();
+                match parser.process()? {
+                    Tag(name, Type::Start, attributes) => Ok(Tag(name, Type::Empty, attributes)),
+                    _ => {
+                        raise!(parser, "found an unexpected ending of a tag");
+                    }
+                }
+            }
+            _ => new(content).lenient().process(),
+        }
+    }
+}
 
 impl<'l> Parser<'l> {
     #[inline]
     fn new(content: &'l str) -> Self {
         Parser {
             reader: Reader::new(content),
+            lenient: false,
+            spacing: false,
+            max_attributes: None,
         }
     }
 
+    #[inline]
+    fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
     fn process(&mut self) -> Result<Tag<'l>> {
         if self.reader.consume_char('/') {
             self.read_end_tag()
@@ -56,35 +109,67 @@ impl<'l> Parser<'l> {
         }
     }
 
-    fn read_attribute(&mut self) -> Result<Option<(String, String)>> {
+    fn read_attribute(&mut self) -> Result<Option<(String, Value)>> {
+        let lenient = self.lenient;
         let attribute = self
             .reader
-            .capture(|reader| reader.consume_attribute())
+            .capture(|reader| {
+                reader.consume_name()
+                    && reader.consume_equality()
+                    && (reader.consume_attribute_value()
+                        || lenient && reader.consume_unquoted_attribute_value())
+            })
             .and_then(|attribute| Some(String::from(attribute)));
         match attribute {
             Some(attribute) => {
                 let k = (&attribute).find('=').unwrap();
                 let name = (&attribute[0..k]).trim_end();
                 let value = (&attribute[(k + 1)..]).trim_start();
-                let value = &value[1..(value.len() - 1)];
-                Ok(Some((String::from(name), String::from(value))))
+                let (value, quote) = if value.starts_with('\'') {
+                    (&value[1..(value.len() - 1)], Some(Quote::Single))
+                } else if value.starts_with('"') {
+                    (&value[1..(value.len() - 1)], Some(Quote::Double))
+                } else {
+                    (value, None)
+                };
+                let value = Value::from(xml::unescape_lenient(value).into_owned());
+                let value = match quote {
+                    Some(quote) => value.with_quote(quote),
+                    _ => value,
+                };
+                Ok(Some((String::from(name), value)))
             }
             _ => Ok(None),
         }
     }
 
     fn read_attributes(&mut self) -> Result<Attributes> {
-        let mut attributes = Attributes::new();
-        loop {
-            self.reader.consume_whitespace();
+        let mut attributes = Vec::new();
+        let trailing = loop {
+            let before = self
+                .reader
+                .capture_raw(|reader| reader.consume_whitespace())
+                .unwrap_or("");
             match self.read_attribute()? {
-                Some((name, value)) => {
-                    attributes.insert(name, value.into());
+                Some(_) if Some(attributes.len()) == self.max_attributes => {
+                    raise!(self, "found more than {} attributes", attributes.len());
                 }
-                _ => break,
+                Some((name, value)) => attributes.push((name, value, before)),
+                _ => break before,
             }
-        }
-        Ok(attributes)
+        };
+        let count = attributes.len();
+        Ok(attributes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, value, before))| match self.spacing {
+                true => {
+                    let after = if i + 1 == count { trailing } else { "" };
+                    (name, value.with_source_spacing(before, after, i))
+                }
+                false => (name, value),
+            })
+            .collect())
     }
 
     fn read_end_tag(&mut self) -> Result<Tag<'l>> {
@@ -118,10 +203,15 @@ impl<'l> Parser<'l> {
 }
 
 macro_rules! implement {
-    ($($const_name:ident: $tag_name:expr,)*) => ($(
-        #[doc = $tag_name]
-        pub const $const_name: &'static str = $tag_name;
-    )*);
+    ($($const_name:ident: $tag_name:expr,)*) => (
+        $(
+            #[doc = $tag_name]
+            pub const $const_name: &'static str = $tag_name;
+        )*
+
+        /// The names of all known elements.
+        pub(crate) const NAMES: &[&str] = &[$($tag_name,)*];
+    );
 }
 
 implement! {
@@ -143,6 +233,7 @@ implement! {
     Link: "a",
     Marker: "marker",
     Mask: "mask",
+    Metadata: "metadata",
     MotionPath: "mpath",
     Path: "path",
     Pattern: "pattern",
@@ -154,6 +245,7 @@ implement! {
     Stop: "stop",
     Style: "style",
     SVG: "svg",
+    Switch: "switch",
     Symbol: "symbol",
     Text: "text",
     TextPath: "textPath",
@@ -202,5 +294,23 @@ mod tests {
         test!("foo= \"bar\"", "foo", "bar");
         test!("foo\t=\n'bar'  ", "foo", "bar");
         test!("標籤='數值'", "標籤", "數值");
+        test!("foo='&lt;bar&amp;'", "foo", "<bar&");
+    }
+
+    #[test]
+    fn tag_parse_lenient() {
+        let Tag(name, kind, attributes) = Tag::parse_lenient("rect width=10 height=20/").unwrap();
+        assert_eq!(name, "rect");
+        assert_eq!(kind, Type::Empty);
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(&*attributes["width"], "10");
+        assert_eq!(&*attributes["height"], "20");
+
+        let Tag(_, kind, attributes) = Tag::parse_lenient("a href=x/y.svg title='z'").unwrap();
+        assert_eq!(kind, Type::Start);
+        assert_eq!(&*attributes["href"], "x/y.svg");
+        assert_eq!(&*attributes["title"], "z");
+
+        assert!(Tag::parse("rect width=10/").is_err());
     }
 }
@@ -0,0 +1,103 @@
+//! The text layout.
+
+use crate::node::element::{GenericElement, Text};
+use crate::node::{Length, Node};
+use crate::xml;
+
+/// The font size assumed when the `font-size` attribute is absent.
+pub const DEFAULT_FONT_SIZE: f64 = 16.0;
+
+/// A source of glyph metrics used for laying out text.
+///
+/// The crate has no knowledge of fonts, and it is up to the user to provide
+/// the metrics of the font that will eventually be used for rendering.
+pub trait FontMetrics {
+    /// Return the advance width of a character at a font size.
+    fn advance(&self, ch: char, size: f64) -> f64;
+}
+
+/// A layout of a `text` element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLayout {
+    /// The x coordinates at which the characters start.
+    pub positions: Vec<f64>,
+    /// The bounding box as `(x, y, width, height)`.
+    ///
+    /// Vertically, the box spans one font size above the baseline.
+    pub bounding_box: (f64, f64, f64, f64),
+}
+
+impl<'l> Text<'l> {
+    /// Lay out the text content on a single line.
+    ///
+    /// The line starts at the `x` and `y` attributes and uses the `font-size`
+    /// attribute, which default to zero, zero, and `DEFAULT_FONT_SIZE`,
+    /// respectively. The content of nested elements, such as `tspan`, is
+    /// included, but their positioning attributes are not taken into account.
+    pub fn layout(&self, metrics: &dyn FontMetrics) -> TextLayout {
+        let attribute = |name: &str| {
+            self.inner
+                .attributes
+                .get(name)
+                .and_then(|value| Length::parse(value).ok())
+                .map(|length| length.number)
+        };
+        let x = attribute("x").unwrap_or(0.0);
+        let y = attribute("y").unwrap_or(0.0);
+        let size = attribute("font-size").unwrap_or(DEFAULT_FONT_SIZE);
+        let mut content = String::new();
+        collect(&self.inner, &mut content);
+        let mut positions = Vec::new();
+        let mut advance = x;
+        for ch in content.chars() {
+            positions.push(advance);
+            advance += metrics.advance(ch, size);
+        }
+        TextLayout {
+            positions,
+            bounding_box: (x, y - size, advance - x, size),
+        }
+    }
+}
+
+fn collect(element: &GenericElement, content: &mut String) {
+    for child in element.get_children() {
+        match child {
+            Node::Text(text) => content.push_str(&xml::unescape_lenient(text)),
+            Node::Element(element) => collect(element, content),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FontMetrics, TextLayout};
+    use crate::node::element::Text;
+    use crate::node::Node;
+
+    struct Monospace;
+
+    impl FontMetrics for Monospace {
+        fn advance(&self, _: char, size: f64) -> f64 {
+            0.5 * size
+        }
+    }
+
+    #[test]
+    fn text_layout() {
+        let text = Text::new()
+            .set("x", 10)
+            .set("y", "20px")
+            .set("font-size", 10)
+            .add(Node::new_text("a&amp;b"));
+
+        assert_eq!(
+            text.layout(&Monospace),
+            TextLayout {
+                positions: vec![10.0, 15.0, 20.0],
+                bounding_box: (10.0, 10.0, 15.0, 10.0),
+            },
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! The processing instructions.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::events::parser::{Error, Reader, Result};
+use crate::node::{Node, Quote, Value};
+use crate::xml;
+
+/// A processing instruction split into its target and pseudo-attributes.
+///
+/// The content corresponds to that of `Event::Instruction` and
+/// `Node::Instruction`, that is, it excludes the `<?` and `?>` delimiters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    target: String,
+    attributes: Vec<(String, Value)>,
+}
+
+impl Instruction {
+    /// Create an instruction.
+    #[inline]
+    pub fn new<T: Into<String>>(target: T) -> Self {
+        Instruction {
+            target: target.into(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Create an [`xml-stylesheet`](https://www.w3.org/TR/xml-stylesheet/)
+    /// instruction.
+    pub fn stylesheet<T, U>(href: T, kind: U) -> Self
+    where
+        T: Into<Value>,
+        U: Into<Value>,
+    {
+        Instruction::new("xml-stylesheet")
+            .set("href", href)
+            .set("type", kind)
+    }
+
+    /// Parse an instruction.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut reader = Reader::new(content);
+        let target = match reader.capture(|reader| reader.consume_name()) {
+            Some(target) => target,
+            _ => return Err(Error::new(reader.position(), "expected a target")),
+        };
+        let mut instruction = Instruction::new(target);
+        loop {
+            reader.consume_whitespace();
+            let attribute = match reader.capture(|reader| reader.consume_attribute()) {
+                Some(attribute) => attribute,
+                _ => break,
+            };
+            let k = attribute.find('=').unwrap();
+            let name = attribute[..k].trim_end();
+            let value = attribute[(k + 1)..].trim_start();
+            let quote = if value.starts_with('\'') {
+                Quote::Single
+            } else {
+                Quote::Double
+            };
+            let value = xml::unescape_lenient(&value[1..(value.len() - 1)]);
+            instruction = instruction.set(name, Value::from(value.into_owned()).with_quote(quote));
+        }
+        if !reader.is_done() {
+            return Err(Error::new(
+                reader.position(),
+                "found an unexpected ending of an instruction",
+            ));
+        }
+        Ok(instruction)
+    }
+
+    /// Assign a pseudo-attribute, replacing an existing one with the same name.
+    pub fn set<T, U>(mut self, name: T, value: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<Value>,
+    {
+        let name = name.into();
+        let value = value.into();
+        match self.attributes.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.attributes.push((name, value)),
+        }
+        self
+    }
+
+    /// Return the target.
+    #[inline]
+    pub fn get_target(&self) -> &str {
+        &self.target
+    }
+
+    /// Return a pseudo-attribute.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+
+    /// Return the pseudo-attributes in their order of appearance.
+    #[inline]
+    pub fn get_attributes(&self) -> &[(String, Value)] {
+        &self.attributes
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.target.fmt(formatter)?;
+        for (name, value) in &self.attributes {
+            let quote = value.quote().unwrap_or(Quote::Double).as_char();
+            let value = xml::escape_attribute(value, quote);
+            write!(formatter, " {}={}{}{}", name, quote, value, quote)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'l> From<Instruction> for Node<'l> {
+    #[inline]
+    fn from(instruction: Instruction) -> Self {
+        Node::Instruction(Cow::Owned(instruction.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Instruction;
+
+    #[test]
+    fn instruction_parse() {
+        let content = r#"xml-stylesheet href="style.css" type="text/css""#;
+        let instruction = Instruction::parse(content).unwrap();
+
+        assert_eq!(instruction.get_target(), "xml-stylesheet");
+        assert_eq!(&**instruction.get("href").unwrap(), "style.css");
+        assert_eq!(&**instruction.get("type").unwrap(), "text/css");
+        assert_eq!(instruction.to_string(), content);
+        assert_eq!(
+            instruction,
+            Instruction::stylesheet("style.css", "text/css")
+        );
+        assert_eq!(
+            Instruction::stylesheet("style.css", "text/css").to_string(),
+            content,
+        );
+
+        assert!(Instruction::parse(r#"xml-stylesheet href="style.css"?"#).is_err());
+    }
+}
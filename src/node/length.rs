@@ -0,0 +1,151 @@
+//! The lengths.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::events::parser::{Error, Reader, Result};
+use crate::node::Value;
+
+/// A [length](https://www.w3.org/TR/SVG/types.html#InterfaceSVGLength).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Length {
+    /// The number.
+    pub number: f64,
+    /// The unit.
+    pub unit: Unit,
+}
+
+/// A unit of a length.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Unit {
+    /// No unit, that is, user units.
+    None,
+    /// `em`.
+    Em,
+    /// `ex`.
+    Ex,
+    /// `px`.
+    Px,
+    /// `in`.
+    In,
+    /// `cm`.
+    Cm,
+    /// `mm`.
+    Mm,
+    /// `pt`.
+    Pt,
+    /// `pc`.
+    Pc,
+    /// `%`.
+    Percent,
+}
+
+impl Length {
+    /// Create a length.
+    #[inline]
+    pub fn new(number: f64, unit: Unit) -> Self {
+        Length { number, unit }
+    }
+
+    /// Parse a length.
+    pub fn parse(content: &str) -> Result<Self> {
+        let content = content.trim();
+        let mut reader = Reader::new(content);
+        let number = match reader.capture(|reader| reader.consume_number()) {
+            Some(number) => number,
+            _ => return Err(Error::new(reader.position(), "expected a number")),
+        };
+        let number = match number.parse() {
+            Ok(number) => number,
+            _ => {
+                let message = format!("failed to parse a number '{}'", number);
+                return Err(Error::new(reader.position(), message));
+            }
+        };
+        let unit = reader.peek_many().as_str();
+        let unit = match unit {
+            "" => Unit::None,
+            "em" => Unit::Em,
+            "ex" => Unit::Ex,
+            "px" => Unit::Px,
+            "in" => Unit::In,
+            "cm" => Unit::Cm,
+            "mm" => Unit::Mm,
+            "pt" => Unit::Pt,
+            "pc" => Unit::Pc,
+            "%" => Unit::Percent,
+            _ => {
+                let message = format!("found an unknown unit '{}'", unit);
+                return Err(Error::new(reader.position(), message));
+            }
+        };
+        Ok(Length { number, unit })
+    }
+}
+
+impl Unit {
+    /// Return the suffix denoting the unit.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Unit::None => "",
+            Unit::Em => "em",
+            Unit::Ex => "ex",
+            Unit::Px => "px",
+            Unit::In => "in",
+            Unit::Cm => "cm",
+            Unit::Mm => "mm",
+            Unit::Pt => "pt",
+            Unit::Pc => "pc",
+            Unit::Percent => "%",
+        }
+    }
+}
+
+impl fmt::Display for Length {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}{}", self.number, self.unit.as_str())
+    }
+}
+
+impl FromStr for Length {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(content: &str) -> Result<Self> {
+        Length::parse(content)
+    }
+}
+
+impl From<Length> for Value {
+    #[inline]
+    fn from(length: Length) -> Self {
+        length.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Length, Unit};
+
+    #[test]
+    fn length_parse() {
+        assert_eq!(
+            "10".parse::<Length>().unwrap(),
+            Length::new(10.0, Unit::None)
+        );
+        assert_eq!(
+            "-1.5px".parse::<Length>().unwrap(),
+            Length::new(-1.5, Unit::Px)
+        );
+        assert_eq!(
+            "50%".parse::<Length>().unwrap(),
+            Length::new(50.0, Unit::Percent)
+        );
+        assert_eq!(Length::new(2.5, Unit::Em).to_string(), "2.5em");
+
+        assert!("px".parse::<Length>().is_err());
+        assert!("10 px".parse::<Length>().is_err());
+        assert!("10furlongs".parse::<Length>().is_err());
+    }
+}
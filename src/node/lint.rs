@@ -0,0 +1,306 @@
+//! The structural linting.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::node::element::{tag, GenericElement};
+use crate::node::{Document, Node};
+
+/// A structural problem found by linting.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Lint {
+    /// An element lacks an attribute without which it renders nothing.
+    MissingAttribute {
+        /// The name of the element.
+        element: String,
+        /// The name of the attribute.
+        attribute: String,
+    },
+    /// An attribute has a value outside of its valid range.
+    OutOfRange {
+        /// The name of the element.
+        element: String,
+        /// The name of the attribute.
+        attribute: String,
+        /// The value of the attribute.
+        value: String,
+    },
+}
+
+/// Attributes that have to be present, per element.
+const REQUIRED: &[(&str, &[&str])] = &[
+    (tag::Circle, &["r"]),
+    (tag::Ellipse, &["rx", "ry"]),
+    (tag::Path, &["d"]),
+    (tag::Polygon, &["points"]),
+    (tag::Polyline, &["points"]),
+    (tag::Rectangle, &["width", "height"]),
+];
+
+/// Attributes that must not be negative, per element.
+const NONNEGATIVE: &[(&str, &[&str])] = &[
+    (tag::Circle, &["r"]),
+    (tag::Ellipse, &["rx", "ry"]),
+    (tag::Rectangle, &["width", "height", "rx", "ry"]),
+];
+
+/// Attributes that have to be within `[0, 1]` on any element.
+const UNIT: &[&str] = &["opacity", "fill-opacity", "stroke-opacity", "stop-opacity"];
+
+impl<'l> Document<'l> {
+    /// Check the document for common structural mistakes.
+    ///
+    /// This is not a validation against a schema. The following is flagged:
+    ///
+    /// * `circle`, `ellipse`, `rect`, `path`, `polygon`, and `polyline`
+    ///   elements missing the attributes defining their geometry;
+    /// * negative radii, widths, and heights; and
+    /// * opacities outside of `[0, 1]`.
+    pub fn lint(&self) -> Vec<Lint> {
+        let mut lints = Vec::new();
+        lint(self.get_svg(), &mut lints);
+        lints
+    }
+
+    /// Return the ids assigned to more than one element, in the order of their
+    /// first appearance.
+    pub fn duplicate_ids(&self) -> Vec<String> {
+        let mut ids = Vec::new();
+        let mut counts = HashMap::new();
+        collect_ids(self.get_svg(), &mut ids, &mut counts);
+        ids.into_iter()
+            .filter(|id| counts[id] > 1)
+            .map(String::from)
+            .collect()
+    }
+
+    /// Return the ids referred to by any element.
+    ///
+    /// References are `url(#id)` anywhere in the values of attributes, which
+    /// covers properties such as `fill` and `clip-path` as well as the `style`
+    /// attribute, anywhere in the text of `style` elements, and local
+    /// references in `href` and `xlink:href`.
+    pub fn referenced_ids(&self) -> HashSet<String> {
+        let mut ids = HashSet::new();
+        collect_references(self.get_svg(), &mut ids);
+        ids
+    }
+}
+
+/// Return the ids referred to by the attributes of an element, or by the
+/// style sheet if the element is a `style` element, excluding other
+/// children.
+pub(crate) fn references<'a>(element: &'a GenericElement) -> Vec<&'a str> {
+    let mut ids = Vec::new();
+    for (name, value) in element.get_attributes() {
+        if name == "href" || name == "xlink:href" {
+            if let Some(id) = value.trim().strip_prefix('#') {
+                ids.push(id);
+            }
+            continue;
+        }
+        url_references(value, &mut ids);
+    }
+    if element.get_name() == tag::Style {
+        for child in element.get_children() {
+            if let Node::Text(content) | Node::RawText(content) = child {
+                url_references(content, &mut ids);
+            }
+        }
+    }
+    ids.retain(|id| !id.is_empty());
+    ids
+}
+
+fn url_references<'a>(content: &'a str, ids: &mut Vec<&'a str>) {
+    let mut rest = content;
+    while let Some(start) = rest.find("url(") {
+        rest = &rest[(start + 4)..];
+        let end = match rest.find(')') {
+            Some(end) => end,
+            _ => break,
+        };
+        let reference = rest[..end].trim();
+        let reference = ['"', '\'']
+            .iter()
+            .find_map(|&quote| reference.strip_prefix(quote)?.strip_suffix(quote))
+            .unwrap_or(reference);
+        if let Some(id) = reference.strip_prefix('#') {
+            ids.push(id);
+        }
+        rest = &rest[end..];
+    }
+}
+
+pub(crate) fn collect_references(element: &GenericElement, ids: &mut HashSet<String>) {
+    ids.extend(references(element).into_iter().map(String::from));
+    for child in element.get_children() {
+        if let Node::Element(child) = child {
+            collect_references(child, ids);
+        }
+    }
+}
+
+fn lint(element: &GenericElement, lints: &mut Vec<Lint>) {
+    let name = element.get_name();
+    let attributes = element.get_attributes();
+    for (_, required) in REQUIRED.iter().filter(|(element, _)| *element == name) {
+        for attribute in required.iter() {
+            if !attributes.contains_key(*attribute) {
+                lints.push(Lint::MissingAttribute {
+                    element: name.into(),
+                    attribute: (*attribute).into(),
+                });
+            }
+        }
+    }
+    let mut check = |attribute: &str, valid: fn(f64) -> bool| {
+        if let Some(value) = attributes.get(attribute) {
+            if matches!(parse_number(value), Some(number) if !valid(number)) {
+                lints.push(Lint::OutOfRange {
+                    element: name.into(),
+                    attribute: attribute.into(),
+                    value: value.to_string(),
+                });
+            }
+        }
+    };
+    for (_, nonnegative) in NONNEGATIVE.iter().filter(|(element, _)| *element == name) {
+        for attribute in nonnegative.iter() {
+            check(attribute, |number| number >= 0.0);
+        }
+    }
+    for attribute in UNIT {
+        check(attribute, |number| (0.0..=1.0).contains(&number));
+    }
+    for child in element.get_children() {
+        if let Node::Element(child) = child {
+            lint(child, lints);
+        }
+    }
+}
+
+fn collect_ids<'l>(
+    element: &'l GenericElement,
+    ids: &mut Vec<&'l str>,
+    counts: &mut HashMap<&'l str, usize>,
+) {
+    if let Some(id) = element.get_attributes().get("id") {
+        let count = counts.entry(id).or_insert(0);
+        if *count == 0 {
+            ids.push(id);
+        }
+        *count += 1;
+    }
+    for child in element.get_children() {
+        if let Node::Element(child) = child {
+            collect_ids(child, ids, counts);
+        }
+    }
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+        .unwrap_or(value.len());
+    let number = value[..end].parse::<f64>().ok()?;
+    match &value[end..] {
+        "%" => Some(number / 100.0),
+        _ => Some(number),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lint;
+    use crate::node::element::{Circle, Group, Rectangle};
+    use crate::Document;
+
+    #[test]
+    fn referenced_ids() {
+        let document = Document::parse(
+            r##"<svg xmlns:xlink="http://www.w3.org/1999/xlink">
+<defs>
+<linearGradient id="gradient"/>
+<clipPath id="clip"/>
+<circle id="dot" r="1"/>
+<filter id="unused"/>
+</defs>
+<rect fill="url(#gradient)" clip-path="url( '#clip' )" width="1" height="1"/>
+<use xlink:href="#dot" style="stroke: url(#missing) none"/>
+<a href="https://example.com/#anchor"/>
+</svg>"##,
+        )
+        .unwrap();
+
+        let mut ids = document.referenced_ids().into_iter().collect::<Vec<_>>();
+        ids.sort();
+        assert_eq!(ids, vec!["clip", "dot", "gradient", "missing"]);
+    }
+
+    #[test]
+    fn lint_circle_without_radius() {
+        let document = Document::new().add(Circle::new().set("cx", 10).set("cy", 10));
+
+        assert_eq!(
+            document.lint(),
+            vec![Lint::MissingAttribute {
+                element: "circle".into(),
+                attribute: "r".into(),
+            }],
+        );
+    }
+
+    #[test]
+    fn lint_rectangle_with_negative_width() {
+        let document = Document::new().add(
+            Rectangle::new()
+                .set("width", "-10px")
+                .set("height", 20)
+                .set("opacity", 0.5),
+        );
+
+        assert_eq!(
+            document.lint(),
+            vec![Lint::OutOfRange {
+                element: "rect".into(),
+                attribute: "width".into(),
+                value: "-10px".into(),
+            }],
+        );
+    }
+
+    #[test]
+    fn lint_percentage_opacity() {
+        let document = Document::new().add(
+            Circle::new()
+                .set("r", 1)
+                .set("opacity", "50%")
+                .set("fill-opacity", "150%"),
+        );
+
+        assert_eq!(
+            document.lint(),
+            vec![Lint::OutOfRange {
+                element: "circle".into(),
+                attribute: "fill-opacity".into(),
+                value: "150%".into(),
+            }],
+        );
+    }
+
+    #[test]
+    fn duplicate_ids() {
+        let document = Document::new()
+            .add(Circle::new().set("id", "x"))
+            .add(
+                Group::new()
+                    .set("id", "y")
+                    .add(Rectangle::new().set("id", "x")),
+            )
+            .add(Circle::new().set("id", "x"));
+
+        assert_eq!(document.duplicate_ids(), vec!["x".to_string()]);
+        assert!(Document::new().duplicate_ids().is_empty());
+    }
+}
@@ -3,20 +3,47 @@
 use std::borrow::Cow;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
-use std::iter::once;
+use std::iter::{once, FromIterator};
 
 pub use parser::error::Error;
+pub use parser::Limits;
 
 use crate::events;
-use crate::events::Event;
+use crate::events::{CompactEvent, Event, OwnedEvent};
 use crate::node::element::GenericElement;
 use crate::node::parser::Parser;
 
-pub use self::value::Value;
-
-mod parser;
+pub use self::aspect::{Align, MeetOrSlice, PreserveAspectRatio};
+pub use self::color::Color;
+pub use self::doctype::Doctype;
+pub use self::instruction::Instruction;
+pub use self::length::{Length, Unit};
+pub use self::lint::Lint;
+pub use self::style::parse_style;
+pub use self::timing::Timing;
+pub use self::transform::{parse_transform, Transform, TransformOp};
+pub use self::value::{Quote, Value};
+pub use self::viewport::Mapping;
+pub use self::xml_declaration::XmlDeclaration;
+
+mod aspect;
+pub mod cleanup;
+mod color;
+mod compare;
+mod doctype;
+mod instruction;
+mod length;
+mod lint;
+mod parallel;
+pub mod parser;
+mod style;
+mod timing;
+mod transform;
 mod value;
+mod viewport;
+mod xml_declaration;
 
 /// Attributes.
 pub type Attributes = HashMap<String, Value>;
@@ -35,6 +62,13 @@ pub struct Document<'l> {
     misc_followers: Vec<Node<'l>>,
 }
 
+impl<'l> Default for Document<'l> {
+    #[inline]
+    fn default() -> Self {
+        Document::new()
+    }
+}
+
 impl<'l> Document<'l> {
     #[inline]
     pub fn new() -> Document<'l> {
@@ -46,9 +80,7 @@ impl<'l> Document<'l> {
     }
 
     pub fn from_event_parser(parser: events::parser::Parser<'l>) -> Result<Document<'l>> {
-        let events = parser
-            .collect::<events::parser::Result<Vec<_>>>()
-            .map_err(|err| Error::new(err.to_string()))?;
+        let events = parser.collect::<events::parser::Result<Vec<_>>>()?;
         Self::from_events(events.into_iter())
     }
 
@@ -56,6 +88,67 @@ impl<'l> Document<'l> {
         Parser::new(events).process()
     }
 
+    /// Parse a document.
+    pub fn parse(content: &'l str) -> Result<Document<'l>> {
+        Self::from_event_parser(events::parser::Parser::new(content))
+    }
+
+    /// Parse a document from untrusted content, failing with an error when
+    /// the content exceeds the given limits.
+    pub fn parse_with_limits(content: &'l str, limits: Limits) -> Result<Document<'l>> {
+        let mut parser = events::parser::Parser::new(content);
+        if let Some(max_attributes) = limits.max_attributes {
+            parser = parser.max_attributes(max_attributes);
+        }
+        let events = parser.collect::<events::parser::Result<Vec<_>>>()?;
+        Parser::new(events.into_iter()).limits(limits).process()
+    }
+
+    /// Parse a fragment, that is, any sequence of top-level nodes.
+    ///
+    /// Unlike a document, a fragment is not required to have exactly one
+    /// `<svg>` element, which makes it suitable for snippets with several
+    /// sibling roots.
+    pub fn parse_fragment(content: &'l str) -> Result<Vec<Node<'l>>> {
+        let events =
+            events::parser::Parser::new(content).collect::<events::parser::Result<Vec<_>>>()?;
+        Parser::new(events.into_iter()).process_fragment()
+    }
+
+    /// Replace the prolog with an XML declaration and the SVG 1.1 document
+    /// type declaration.
+    pub fn with_standard_prolog(mut self) -> Self {
+        self.prolog = vec![
+            XmlDeclaration::new("1.0").encoding("utf-8").into(),
+            Doctype::svg_1_1().into(),
+        ];
+        self
+    }
+
+    /// Append a node to the prolog.
+    pub fn push_prolog<T>(&mut self, node: T)
+    where
+        T: Into<Node<'l>>,
+    {
+        self.prolog.push(node.into());
+    }
+
+    /// Insert a node into the prolog at a position.
+    ///
+    /// The prolog is composed in order. The conventional order is the XML
+    /// declaration first, then the document type declaration, and then any
+    /// comments and processing instructions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of nodes in the prolog.
+    pub fn insert_prolog<T>(&mut self, index: usize, node: T)
+    where
+        T: Into<Node<'l>>,
+    {
+        self.prolog.insert(index, node.into());
+    }
+
     /// Append a node.
     pub fn add<T>(mut self, node: T) -> Self
     where
@@ -65,6 +158,18 @@ impl<'l> Document<'l> {
         self
     }
 
+    /// Append several nodes.
+    pub fn add_all<I>(mut self, nodes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Node<'l>>,
+    {
+        for node in nodes {
+            self.svg.append(node.into());
+        }
+        self
+    }
+
     /// Assign an attribute.
     #[inline]
     pub fn set<T, U>(mut self, name: T, value: U) -> Self
@@ -88,6 +193,53 @@ impl<'l> Document<'l> {
         &mut self.svg
     }
 
+    /// Find an element by its `id` attribute.
+    pub fn get_element_by_id(&self, id: &str) -> Option<&GenericElement<'l>> {
+        self.svg.get_element_by_id(id)
+    }
+
+    /// Resolve the element referenced by a `use` element.
+    ///
+    /// The reference is read from the `href` attribute or, failing that, from
+    /// the `xlink:href` attribute, and it has to be a local one (`#id`).
+    pub fn resolve_use(&self, use_element: &GenericElement) -> Option<&GenericElement<'l>> {
+        let attributes = use_element.get_attributes();
+        let reference = attributes
+            .get("href")
+            .or_else(|| attributes.get("xlink:href"))?;
+        let id = reference.trim().strip_prefix('#')?;
+        self.get_element_by_id(id)
+    }
+
+    /// Return the events of the document, borrowing the attributes instead of
+    /// copying them as `to_events` does.
+    pub fn to_compact_events(&'l self) -> impl Iterator<Item = CompactEvent<'l>> {
+        let prolog_events = self.prolog.iter().flat_map(|node| node.to_compact_events());
+        let svg_events = self.svg.to_compact_events();
+        let misc_follower_events = self
+            .misc_followers
+            .iter()
+            .flat_map(|node| node.to_compact_events());
+
+        prolog_events.chain(svg_events).chain(misc_follower_events)
+    }
+
+    /// Return the events of the document owning their content, so that they
+    /// can be kept after the document is gone.
+    pub fn events_owned(&self) -> impl Iterator<Item = OwnedEvent> + '_ {
+        let prolog_events = self.prolog.iter().flat_map(|node| node.to_compact_events());
+        let svg_events = self.svg.to_compact_events();
+        let misc_follower_events = self
+            .misc_followers
+            .iter()
+            .flat_map(|node| node.to_compact_events());
+
+        prolog_events
+            .chain(svg_events)
+            .chain(misc_follower_events)
+            .map(OwnedEvent::from)
+    }
+
     pub fn to_events(&'l self) -> impl Iterator<Item = Event<'l>> {
         let prolog_events = self.prolog.iter().flat_map(|node| node.to_events());
         let svg_events = self.svg.to_events();
@@ -95,6 +247,34 @@ impl<'l> Document<'l> {
 
         prolog_events.chain(svg_events).chain(misc_follower_events)
     }
+
+    /// Clone the document sharing the children of the root element.
+    ///
+    /// The prolog and the attributes of the root element are copied, whereas
+    /// the children are shared with the original until either document
+    /// modifies them, at which point they are copied. This makes it cheap to
+    /// derive many documents from a template differing in root attributes.
+    /// Unlike `clone`, which copies the whole tree, the two documents alias
+    /// the same children in memory but never observe each other's changes.
+    pub fn shallow_clone(&self) -> Document<'l> {
+        Document {
+            prolog: self.prolog.clone(),
+            svg: Clone::clone(&self.svg),
+            misc_followers: self.misc_followers.clone(),
+        }
+    }
+}
+
+impl<'l> Clone for Document<'l> {
+    /// Clone the document copying the whole tree, so that no children are
+    /// shared with the original.
+    fn clone(&self) -> Self {
+        Document {
+            prolog: self.prolog.clone(),
+            svg: self.svg.deep_clone(),
+            misc_followers: self.misc_followers.clone(),
+        }
+    }
 }
 
 impl<'l> From<GenericElement<'l>> for Document<'l> {
@@ -108,6 +288,33 @@ impl<'l> From<GenericElement<'l>> for Document<'l> {
     }
 }
 
+impl<'l> TryFrom<&'l str> for Document<'l> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(content: &'l str) -> Result<Self> {
+        Document::parse(content)
+    }
+}
+
+impl<'l> fmt::Display for Document<'l> {
+    /// Write the whole document, including the prolog and the nodes following
+    /// the root element, as `svg::write` does.
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let mut displayed = Vec::new();
+        crate::write(&mut displayed, self).map_err(|_error| fmt::Error)?;
+        write!(formatter, "{}", String::from_utf8_lossy(&displayed))
+    }
+}
+
+impl<'l> FromIterator<Event<'l>> for Result<Document<'l>> {
+    /// Build a document from events; see `Document::from_events`.
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Event<'l>>>(events: T) -> Self {
+        Document::from_events(events.into_iter())
+    }
+}
+
 impl<'l> AsRef<Document<'l>> for Document<'l> {
     fn as_ref(&self) -> &Document<'l> {
         self
@@ -122,6 +329,8 @@ pub enum Node<'l> {
     Element(GenericElement<'l>),
     /// A text node.
     Text(Cow<'l, str>),
+    /// A text node written as is, without escaping.
+    RawText(Cow<'l, str>),
     /// A padded comment (eg. `<!-- foo -->`).
     Comment(Cow<'l, str>),
     /// An unpadded comment (eg. `<!--foo-->`).
@@ -143,6 +352,13 @@ impl<'l> Node<'l> {
         Node::Text(content.into())
     }
 
+    /// Creates a text node written as is, without escaping, which is up to
+    /// the caller to keep well-formed.
+    #[inline]
+    pub fn new_raw_text<T: Into<Cow<'l, str>>>(content: T) -> Self {
+        Node::RawText(content.into())
+    }
+
     /// Create a comment node. The content will be padded (eg. `new_comment("foo")` would be
     /// `<!-- foo -->` in XML).
     #[inline]
@@ -169,10 +385,74 @@ impl<'l> Node<'l> {
         Node::Instruction(content.into())
     }
 
+    /// Return the element if the node is one.
+    #[inline]
+    pub fn as_element(&self) -> Option<&GenericElement<'l>> {
+        match self {
+            Node::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    /// Return the element if the node is one, for modification.
+    #[inline]
+    pub fn as_mut_element(&mut self) -> Option<&mut GenericElement<'l>> {
+        match self {
+            Node::Element(element) => Some(element),
+            _ => None,
+        }
+    }
+
+    /// Check if the node is text consisting only of whitespace, such as
+    /// indentation.
+    #[inline]
+    pub(crate) fn is_blank(&self) -> bool {
+        matches!(self, Node::Text(content) if content.trim().is_empty())
+    }
+
+    /// Return the content of a comment and whether it is padded.
+    pub fn comment_content(&self) -> Option<(&str, bool)> {
+        match self {
+            Node::Comment(content) => Some((content, true)),
+            Node::UnpaddedComment(content) => Some((content, false)),
+            _ => None,
+        }
+    }
+
+    /// Make a comment padded or unpadded, leaving other nodes as they are.
+    pub fn set_comment_padded(&mut self, padded: bool) {
+        let content = match self {
+            Node::Comment(content) | Node::UnpaddedComment(content) => std::mem::take(content),
+            _ => return,
+        };
+        *self = if padded {
+            Node::Comment(content)
+        } else {
+            Node::UnpaddedComment(content)
+        };
+    }
+
+    /// Return the events of the node, borrowing the attributes instead of
+    /// copying them as `to_events` does.
+    pub fn to_compact_events(&'l self) -> Box<dyn Iterator<Item = CompactEvent<'l>> + 'l> {
+        match self {
+            Node::Element(element) => element.to_compact_events(),
+            Node::Text(content) => Box::new(once(CompactEvent::Text(content))),
+            Node::RawText(content) => Box::new(once(CompactEvent::RawText(content))),
+            Node::Comment(content) => Box::new(once(CompactEvent::Comment(content))),
+            Node::UnpaddedComment(content) => {
+                Box::new(once(CompactEvent::UnpaddedComment(content)))
+            }
+            Node::Declaration(content) => Box::new(once(CompactEvent::Declaration(content))),
+            Node::Instruction(content) => Box::new(once(CompactEvent::Instruction(content))),
+        }
+    }
+
     pub fn to_events(&'l self) -> Box<dyn Iterator<Item = Event<'l>> + 'l> {
         match self {
             Node::Element(element) => element.to_events(),
             Node::Text(content) => Box::new(once(Event::Text(content))),
+            Node::RawText(content) => Box::new(once(Event::RawText(content))),
             Node::Comment(content) => Box::new(once(Event::Comment(content))),
             Node::UnpaddedComment(content) => Box::new(once(Event::UnpaddedComment(content))),
             Node::Declaration(content) => Box::new(once(Event::Declaration(content))),
@@ -251,6 +531,18 @@ macro_rules! node(
                 self
             }
 
+            /// Append several nodes.
+            pub fn add_all<I>(mut self, nodes: I) -> Self
+            where
+                I: IntoIterator,
+                I::Item: std::convert::Into<crate::node::Node<'l>>,
+            {
+                for node in nodes {
+                    crate::node::Element::append(&mut self, node.into());
+                }
+                self
+            }
+
             /// Assign an attribute.
             #[inline]
             pub fn set<T, U>(mut self, name: T, value: U) -> Self
@@ -262,6 +554,74 @@ macro_rules! node(
                 self
             }
 
+            /// Pass the node through a function, which keeps a chain of
+            /// builder calls going.
+            #[inline]
+            pub fn apply<F>(self, f: F) -> Self
+            where
+                F: FnOnce(Self) -> Self,
+            {
+                f(self)
+            }
+
+            /// Pass the node through a function if the condition holds.
+            #[inline]
+            pub fn apply_if<F>(self, condition: bool, f: F) -> Self
+            where
+                F: FnOnce(Self) -> Self,
+            {
+                if condition {
+                    f(self)
+                } else {
+                    self
+                }
+            }
+
+            /// Assign an attribute in place, which is the same as
+            /// `Element::assign` but does not require the trait in scope.
+            #[inline]
+            pub fn set_attr<T, U>(&mut self, name: T, value: U)
+            where
+                T: Into<String>,
+                U: Into<crate::node::Value>,
+            {
+                crate::node::Element::assign(self, name, value);
+            }
+
+            /// Assign the `stroke-dasharray` and `stroke-dashoffset` attributes.
+            #[inline]
+            pub fn set_dash(mut self, dash: crate::node::element::Dash) -> Self {
+                self.$field_name.set_dash(dash);
+                self
+            }
+
+            /// Fill with the pattern or gradient with the given `id`.
+            #[inline]
+            pub fn fill_pattern(self, id: &str) -> Self {
+                self.set("fill", format!("url(#{})", id))
+            }
+
+            /// Assign the `requiredFeatures` attribute.
+            #[inline]
+            pub fn set_required_features(mut self, features: &[&str]) -> Self {
+                self.$field_name.set_required_features(features);
+                self
+            }
+
+            /// Assign the `requiredExtensions` attribute.
+            #[inline]
+            pub fn set_required_extensions(mut self, extensions: &[&str]) -> Self {
+                self.$field_name.set_required_extensions(extensions);
+                self
+            }
+
+            /// Assign the `systemLanguage` attribute.
+            #[inline]
+            pub fn set_system_language(mut self, languages: &[&str]) -> Self {
+                self.$field_name.set_system_language(languages);
+                self
+            }
+
             /// Return the inner element.
             #[inline]
             pub fn get_inner(&'l self) -> &'l GenericElement {
@@ -352,12 +712,15 @@ pub mod element;
 mod tests {
     use crate::events::Event;
     use crate::node::element::tag::Type;
-    use crate::node::element::{Path, SVG};
-    use crate::node::Attributes;
+    use crate::node::element::{
+        Definitions, GenericElement, Group, Path, Rectangle, Symbol, Use, SVG,
+    };
+    use crate::node::{Attributes, Element, Limits, Node};
     use crate::{Composer, Document, Parser};
 
+    use std::borrow::Cow;
     use std::collections::HashMap;
-    use std::convert::TryInto;
+    use std::convert::{TryFrom, TryInto};
     use std::fs;
 
     #[test]
@@ -383,6 +746,129 @@ mod tests {
         assert!(svg.get_inner().get_attributes().is_empty());
     }
 
+    #[test]
+    fn collect_events() {
+        let events = vec![
+            Event::Tag("svg", Type::Start, HashMap::new()),
+            Event::Text("text"),
+            Event::Tag("svg", Type::End, HashMap::new()),
+        ];
+        let document = events
+            .into_iter()
+            .collect::<super::Result<Document>>()
+            .unwrap();
+        assert_eq!(document.get_svg().to_string(), "<svg>\ntext\n</svg>");
+
+        let collect = || -> super::Result<Document> {
+            Parser::new(r#"<svg><g id="a"/></svg>"#)
+                .collect::<crate::events::parser::Result<Vec<_>>>()?
+                .into_iter()
+                .collect()
+        };
+        assert_eq!(collect().unwrap().get_svg().get_children().len(), 1);
+
+        let events = vec![Event::Tag("svg", Type::Start, HashMap::new())];
+        assert!(events
+            .into_iter()
+            .collect::<super::Result<Document>>()
+            .is_err());
+    }
+
+    #[test]
+    fn document_display() {
+        let content = r#"<?xml version="1.0"?>
+<svg><rect width="1"/></svg>
+<!-- follower -->"#;
+        let document = Document::parse(content).unwrap();
+        let displayed = format!("{}", document);
+        assert!(displayed.starts_with(r#"<?xml version="1.0"?>"#));
+        assert!(displayed.contains("<svg>\n<rect width=\"1\"/>\n</svg>"));
+        assert!(displayed.ends_with("<!-- follower -->"));
+
+        let mut written = Vec::new();
+        crate::write(&mut written, &document).unwrap();
+        assert_eq!(displayed.as_bytes(), &written[..]);
+    }
+
+    #[test]
+    fn drop_comments() {
+        let events = || {
+            Parser::new(
+                r#"<?xml version="1.0"?>
+<!-- prolog -->
+<svg><!-- child --><g><!--grandchild--><rect/></g><?target?></svg>
+<!-- follower -->"#,
+            )
+            .map(Result::unwrap)
+        };
+
+        let document = super::parser::Parser::new(events())
+            .drop_comments()
+            .process()
+            .unwrap();
+        assert!(document
+            .prolog
+            .iter()
+            .all(|node| matches!(node, Node::Text(_))));
+        assert_eq!(
+            document.get_svg().to_string(),
+            "<svg>\n<g>\n<rect/>\n</g>\n</svg>"
+        );
+        assert!(document
+            .misc_followers
+            .iter()
+            .all(|node| matches!(node, Node::Text(_))));
+
+        let document = Document::from_events(events()).unwrap();
+        assert_eq!(document.get_svg().get_children().len(), 3);
+    }
+
+    #[test]
+    fn reject_exceeded_limits() {
+        let content = format!("{}{}", "<svg>".repeat(100_000), "</svg>".repeat(100_000));
+        let limits = Limits {
+            max_depth: Some(64),
+            ..Limits::default()
+        };
+        assert!(Document::parse_with_limits(&content, limits).is_err());
+
+        let limits = Limits {
+            max_depth: Some(2),
+            max_children: Some(2),
+            max_attributes: Some(2),
+        };
+        let parse = |content| Document::parse_with_limits(content, limits);
+        assert!(parse(r#"<svg a="1" b="2"><g/><g/></svg>"#).is_ok());
+        assert!(parse(r#"<svg><g><rect/></g></svg>"#).is_err());
+        assert!(parse(r#"<svg><g/><g/><g/></svg>"#).is_err());
+        assert!(parse(r#"<svg><g a="1" b="2" c="3"/></svg>"#).is_err());
+
+        let events = Parser::new(r#"<svg><g a="1" b="2" c="3"/></svg>"#).map(Result::unwrap);
+        let limits = Limits {
+            max_attributes: Some(2),
+            ..Limits::default()
+        };
+        assert!(super::parser::Parser::new(events)
+            .limits(limits)
+            .process()
+            .is_err());
+
+        let mut parser = Parser::new(r#"<g a="1" b="2" c="3"/>"#).max_attributes(2);
+        assert!(parser.next().unwrap().is_err());
+
+        let content = "<svg>\n<g a=\"1\" b=\"2\" c=\"3\"/></svg>";
+        let error = match Document::parse_with_limits(content, limits) {
+            Err(error) => error,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "found more than 2 attributes (line 2, column 21)",
+        );
+        assert_eq!(error.offset(), Some(26));
+        assert_eq!(&content[26..], "/></svg>");
+    }
+
     #[test]
     fn reject_no_tags() {
         let events = vec![];
@@ -457,6 +943,183 @@ mod tests {
         assert_eq!(&path4_attributes, path4.get_inner().get_attributes());
     }
 
+    #[test]
+    fn parse() {
+        let content = fs::read_to_string("tests/fixtures/benton.svg").unwrap();
+        let document = Document::try_from(content.as_str()).unwrap();
+
+        let children = document.get_svg().get_children();
+        assert_eq!(children.len(), 4);
+        for child in children {
+            let _: Path = child.clone().try_into().unwrap();
+        }
+
+        assert!(Document::parse("<svg><path></svg>").is_err());
+    }
+
+    #[test]
+    fn with_standard_prolog() {
+        let document = Document::new().with_standard_prolog();
+        let mut composed = Vec::new();
+        crate::write(&mut composed, &document).unwrap();
+
+        assert_eq!(
+            String::from_utf8(composed).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+             <!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">\n\
+             <svg/>",
+        );
+    }
+
+    #[test]
+    fn insert_prolog() {
+        let mut document = Document::new();
+        document.push_prolog(Node::new_comment("foo"));
+        document.insert_prolog(0, super::Doctype::svg_1_1());
+        document.insert_prolog(0, Node::new_instruction(r#"xml version="1.0""#));
+        let mut composed = Vec::new();
+        crate::write(&mut composed, &document).unwrap();
+
+        assert_eq!(
+            String::from_utf8(composed).unwrap(),
+            "<?xml version=\"1.0\"?>\n\
+             <!DOCTYPE svg PUBLIC \"-//W3C//DTD SVG 1.1//EN\" \"http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd\">\n\
+             <!-- foo -->\n\
+             <svg/>",
+        );
+    }
+
+    #[test]
+    fn events_owned() {
+        fn lowercase(element: &mut GenericElement) {
+            element.set_name(element.get_name().to_lowercase());
+            for child in element {
+                if let Node::Element(child) = child {
+                    lowercase(child);
+                }
+            }
+        }
+
+        let events = {
+            let content = String::from("<svg><G><RECT WIDTH='1'/></G></svg>");
+            let mut document = Document::parse(&content).unwrap();
+            lowercase(document.get_mut_svg());
+            document.events_owned().collect::<Vec<_>>()
+        };
+
+        let mut composed = Vec::new();
+        let mut composer = Composer::new(&mut composed);
+        for event in &events {
+            composer.write_compact_event(event.into()).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(composed).unwrap(),
+            "<svg>\n<g>\n<rect WIDTH='1'/>\n</g>\n</svg>",
+        );
+    }
+
+    #[test]
+    fn parse_fragment() {
+        let nodes = Document::parse_fragment("<g id='a'/><g id='b'><path/></g>").unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        for (node, id) in nodes.into_iter().zip(&["a", "b"]) {
+            let group: Group = node.try_into().unwrap();
+            assert_eq!(&*group.get_inner().get_attributes()["id"], *id);
+        }
+        assert!(Document::parse_fragment("<g></path>").is_err());
+    }
+
+    #[test]
+    fn resolve_use() {
+        let document = Document::new()
+            .add(Definitions::new().add(Symbol::new().set("id", "icon")))
+            .add(Use::new().set("href", "#icon"))
+            .add(Use::new().set("xlink:href", "#icon"))
+            .add(Use::new().set("href", "#missing"));
+
+        let children = document.get_svg().get_children();
+        let resolve = |node: &Node| match node {
+            Node::Element(element) => document.resolve_use(element),
+            _ => unreachable!(),
+        };
+        assert_eq!(resolve(&children[1]).unwrap().get_name(), "symbol");
+        assert_eq!(resolve(&children[2]).unwrap().get_name(), "symbol");
+        assert!(resolve(&children[3]).is_none());
+    }
+
+    #[test]
+    fn compact_events() {
+        let mut document = Document::new();
+        for i in 0..100 {
+            let mut path = Path::new();
+            for j in 0..100 {
+                path = path.set(format!("data-{}", j), i * j);
+            }
+            document = document.add(path);
+        }
+
+        let mut expected = Vec::new();
+        let mut composer = Composer::new(&mut expected);
+        document
+            .to_events()
+            .try_for_each(|event| composer.write_event(&event))
+            .unwrap();
+
+        let mut composed = Vec::new();
+        let mut composer = Composer::new(&mut composed);
+        document
+            .to_compact_events()
+            .try_for_each(|event| composer.write_compact_event(event))
+            .unwrap();
+
+        assert_eq!(expected, composed);
+    }
+
+    #[test]
+    fn add_all() {
+        let rectangles = (0..5)
+            .map(|i| Rectangle::new().set("x", 10 * i))
+            .collect::<Vec<_>>();
+        let document = Document::new().add_all(vec![Group::new().add_all(rectangles)]);
+
+        let group = match &document.get_svg().get_children()[0] {
+            Node::Element(group) => group,
+            _ => unreachable!(),
+        };
+        assert_eq!(group.get_children().len(), 5);
+        match &group.get_children()[4] {
+            Node::Element(rectangle) => {
+                assert_eq!(&*rectangle.get_attributes()["x"], "40")
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn shallow_clone() {
+        let template = Document::new()
+            .set("width", 10)
+            .add(Group::new())
+            .add(Path::new());
+
+        let mut document = template.shallow_clone();
+        document.get_mut_svg().assign("width", 20);
+        assert_eq!(&*template.get_svg().get_attributes()["width"], "10");
+        assert_eq!(&*document.get_svg().get_attributes()["width"], "20");
+        assert!(document.get_svg().shares_children(template.get_svg()));
+
+        document.get_mut_svg().append(Path::new());
+        assert!(!document.get_svg().shares_children(template.get_svg()));
+        assert_eq!(template.get_svg().get_children().len(), 2);
+        assert_eq!(document.get_svg().get_children().len(), 3);
+
+        assert!(!template
+            .clone()
+            .get_svg()
+            .shares_children(template.get_svg()));
+    }
+
     #[test]
     fn identity_iterator() {
         let mut destination = Vec::new();
@@ -476,4 +1139,93 @@ mod tests {
         let composed = String::from_utf8(destination).unwrap();
         assert_eq!(contents, composed);
     }
+
+    #[test]
+    fn comment_padding() {
+        let mut node = Node::new_unpadded_comment("x");
+        assert_eq!(node.comment_content(), Some(("x", false)));
+
+        node.set_comment_padded(true);
+        assert_eq!(node.comment_content(), Some(("x", true)));
+
+        let mut destination = Vec::new();
+        let mut composer = Composer::new(&mut destination);
+        node.to_events()
+            .try_for_each(|event| composer.write_event(&event))
+            .unwrap();
+        assert_eq!(String::from_utf8(destination).unwrap(), "<!-- x -->");
+
+        let mut node = Node::new_text("x");
+        node.set_comment_padded(true);
+        assert_eq!(node.comment_content(), None);
+    }
+
+    #[test]
+    fn parse_borrows_content() {
+        let content = "<!--foo--><svg><g>bar</g></svg>";
+        let borrowed = |value: &str| content.as_bytes().as_ptr_range().contains(&value.as_ptr());
+        let document = Document::parse(content).unwrap();
+
+        assert!(matches!(
+            &document.prolog[..],
+            [Node::UnpaddedComment(Cow::Borrowed(value))] if borrowed(value),
+        ));
+        assert!(borrowed(document.get_svg().get_name()));
+        match &document.get_svg().get_children()[..] {
+            [Node::Element(group)] => {
+                assert!(borrowed(group.get_name()));
+                assert!(matches!(
+                    &group.get_children()[..],
+                    [Node::Text(Cow::Borrowed(value))] if borrowed(value),
+                ));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn metadata_text() {
+        let document =
+            Document::new().add(super::element::Metadata::new().add(Node::new_text("a < b & c")));
+        assert!(document
+            .to_string()
+            .contains("<metadata>a &lt; b &amp; c</metadata>"));
+
+        let document = Document::new()
+            .add(super::element::Metadata::new().add(Node::new_raw_text("<dc:title>A</dc:title>")));
+        assert!(document
+            .to_string()
+            .contains("<metadata><dc:title>A</dc:title></metadata>"));
+
+        let content = "<svg><metadata><dc:title>A &amp; B</dc:title></metadata></svg>";
+        let mut parser = Parser::new(content);
+        parser.next();
+        parser.next();
+        assert!(matches!(
+            parser.next(),
+            Some(Ok(Event::Tag("dc:title", _, _)))
+        ));
+    }
+
+    #[test]
+    fn metadata_round_trip() {
+        let content = r#"<svg xmlns="http://www.w3.org/2000/svg">
+<metadata>
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#" xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <rdf:Description rdf:about="" dc:title='A &amp; B'></rdf:Description>
+    <dc:creator/>
+  </rdf:RDF>
+</metadata>
+<rect width="1"/>
+</svg>"#;
+        let document = Document::from_event_parser(Parser::new(content).opaque_metadata()).unwrap();
+        assert!(matches!(
+            &document.get_svg().get_children()[0],
+            Node::Element(element) if matches!(element.get_children()[..], [Node::RawText(_)]),
+        ));
+
+        let mut destination = Vec::new();
+        crate::write(&mut destination, &document).unwrap();
+        assert_eq!(String::from_utf8(destination).unwrap(), content);
+    }
 }
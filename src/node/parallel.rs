@@ -0,0 +1,118 @@
+//! The parallel composition.
+
+use std::thread;
+
+use crate::events::CompactEvent;
+use crate::node::element::tag::Type;
+use crate::node::{Document, Node};
+use crate::Composer;
+
+impl<'l> Document<'l> {
+    /// Compose the document using several threads.
+    ///
+    /// The children of the root element are split into contiguous groups,
+    /// which are composed on separate threads and then concatenated in order.
+    /// The output is the same as that of `svg::write`.
+    pub fn to_string_parallel(&self) -> String {
+        let svg = self.get_svg();
+        let children = svg.get_children();
+        if children.is_empty() {
+            return compose(self.to_compact_events());
+        }
+        let prefix = compose(
+            self.prolog
+                .iter()
+                .flat_map(|node| node.to_compact_events())
+                .chain(Some(CompactEvent::Tag(
+                    svg.get_name(),
+                    Type::Start,
+                    svg.get_attributes(),
+                ))),
+        );
+        let suffix = compose(
+            Some(CompactEvent::Tag(
+                svg.get_name(),
+                Type::End,
+                svg.get_attributes(),
+            ))
+            .into_iter()
+            .chain(
+                self.misc_followers
+                    .iter()
+                    .flat_map(|node| node.to_compact_events()),
+            ),
+        );
+        let threads = thread::available_parallelism().map_or(1, |count| count.get());
+        let size = (children.len() + threads - 1) / threads;
+        let groups = thread::scope(|scope| {
+            children
+                .chunks(size)
+                .map(|group| {
+                    scope.spawn(move || {
+                        compose(
+                            group
+                                .iter()
+                                .flat_map(|node: &Node| node.to_compact_events()),
+                        )
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        let mut content = prefix;
+        for group in groups.iter().chain(Some(&suffix)) {
+            content.push('\n');
+            content.push_str(group);
+        }
+        content
+    }
+}
+
+fn compose<'l, T>(events: T) -> String
+where
+    T: Iterator<Item = CompactEvent<'l>>,
+{
+    let mut content = Vec::new();
+    let mut composer = Composer::new(&mut content);
+    for event in events {
+        composer.write_compact_event(event).unwrap();
+    }
+    String::from_utf8(content).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::element::{Circle, Group, Text};
+    use crate::node::Node;
+    use crate::Document;
+
+    #[test]
+    fn to_string_parallel() {
+        let mut document = Document::new()
+            .with_standard_prolog()
+            .add(Node::new_comment("after"));
+        for i in 0..100 {
+            document = document
+                .add(Circle::new().set("r", i))
+                .add(Group::new().add(Text::new().add(Node::new_text(i.to_string()))));
+        }
+        document = document.add(Node::new_comment("end"));
+
+        let mut expected = Vec::new();
+        crate::write(&mut expected, &document).unwrap();
+        assert_eq!(
+            document.to_string_parallel(),
+            String::from_utf8(expected).unwrap()
+        );
+
+        let document = Document::new();
+        let mut expected = Vec::new();
+        crate::write(&mut expected, &document).unwrap();
+        assert_eq!(
+            document.to_string_parallel(),
+            String::from_utf8(expected).unwrap()
+        );
+    }
+}
@@ -1,6 +1,11 @@
+use std::fmt;
+
+use crate::events;
+
 #[derive(Debug)]
 pub struct Error {
     message: String,
+    offset: Option<usize>,
 }
 
 impl Error {
@@ -8,6 +13,31 @@ impl Error {
     pub fn new<T: Into<String>>(message: T) -> Error {
         Error {
             message: message.into(),
+            offset: None,
+        }
+    }
+
+    /// Return the offset in bytes from the start of the content, if the error
+    /// was found while parsing the content and the offset is known.
+    #[inline]
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+}
+
+impl fmt::Display for Error {
+    #[inline]
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.message.fmt(formatter)
+    }
+}
+
+impl From<events::parser::Error> for Error {
+    #[inline]
+    fn from(error: events::parser::Error) -> Self {
+        Error {
+            message: error.to_string(),
+            offset: error.offset(),
         }
     }
 }
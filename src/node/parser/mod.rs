@@ -1,17 +1,41 @@
+//! The tree builder.
+
 use crate::events::Event;
 use crate::Document;
 
 use super::Result;
 use crate::node::element::tag::Type;
 use crate::node::element::GenericElement;
-use crate::node::Node;
+use crate::node::{Attributes, Node};
 use std::borrow::Cow;
 use std::iter::Peekable;
 
 pub mod error;
 
+/// A builder of documents from events.
 pub struct Parser<'l, T: Iterator<Item = Event<'l>>> {
     events: Peekable<T>,
+    drop_comments: bool,
+    depth: usize,
+    limits: Limits,
+}
+
+/// Limits on the size of documents, which protect against untrusted content.
+///
+/// Exceeding a limit results in an error instead of unbounded recursion or
+/// growth. There are no limits by default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Limits {
+    /// Limit the nesting of elements, where the root element is at depth one.
+    pub max_depth: Option<usize>,
+    /// Limit the number of child nodes of each element, text and comments
+    /// included.
+    pub max_children: Option<usize>,
+    /// Limit the number of attributes of each tag. The tree builder checks
+    /// the attributes of events after they have been collected, which bounds
+    /// the size of the tree; `Document::parse_with_limits` also passes the
+    /// limit to the event parser, which checks it while reading a tag.
+    pub max_attributes: Option<usize>,
 }
 
 macro_rules! raise(
@@ -21,16 +45,47 @@ macro_rules! raise(
 );
 
 impl<'l, T: Iterator<Item = Event<'l>>> Parser<'l, T> {
+    /// Create a builder.
     #[inline]
     pub fn new(events: T) -> Parser<'l, T> {
         Parser {
             events: events.peekable(),
+            drop_comments: false,
+            depth: 0,
+            limits: Limits::default(),
+        }
+    }
+
+    /// Enforce limits on the size of the document.
+    #[inline]
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Leave comments and processing instructions, including the XML
+    /// declaration, out of the tree.
+    #[inline]
+    pub fn drop_comments(mut self) -> Self {
+        self.drop_comments = true;
+        self
+    }
+
+    fn skip_dropped(&mut self) {
+        while self.drop_comments
+            && matches!(
+                self.events.peek(),
+                Some(Event::Comment(_) | Event::UnpaddedComment(_) | Event::Instruction(_))
+            )
+        {
+            self.events.next();
         }
     }
 
     fn process_prolog(&mut self) -> Result<Vec<Node<'l>>> {
         let mut prolog = Vec::new();
         loop {
+            self.skip_dropped();
             let node = match self.events.peek() {
                 None => raise!("no tags in document"),
                 Some(Event::Tag(_, Type::Start | Type::Empty, _)) => break,
@@ -42,6 +97,11 @@ impl<'l, T: Iterator<Item = Event<'l>>> Parser<'l, T> {
                     self.events.next();
                     node
                 }
+                Some(Event::RawText(content)) => {
+                    let node = Node::RawText(Cow::Borrowed(content));
+                    self.events.next();
+                    node
+                }
                 Some(Event::Comment(content)) => {
                     let node = Node::Comment(Cow::Borrowed(content));
                     self.events.next();
@@ -70,10 +130,16 @@ impl<'l, T: Iterator<Item = Event<'l>>> Parser<'l, T> {
 
     fn process_misc_followers(&mut self) -> Result<Vec<Node<'l>>> {
         let mut followers = Vec::new();
-        while let Some(event) = self.events.next() {
+        loop {
+            self.skip_dropped();
+            let event = match self.events.next() {
+                None => break,
+                Some(event) => event,
+            };
             let node = match event {
                 Event::Tag(_, _, _) => raise!("unexpected second top-level tag"),
                 Event::Text(content) => Node::Text(Cow::Borrowed(content)),
+                Event::RawText(content) => Node::RawText(Cow::Borrowed(content)),
                 Event::Comment(content) => Node::Comment(Cow::Borrowed(content)),
                 Event::UnpaddedComment(content) => Node::UnpaddedComment(Cow::Borrowed(content)),
                 Event::Declaration(content) => Node::Declaration(Cow::Borrowed(content)),
@@ -98,6 +164,11 @@ impl<'l, T: Iterator<Item = Event<'l>>> Parser<'l, T> {
                 self.events.next();
                 node
             }
+            Some(Event::RawText(content)) => {
+                let node = Ok(Node::RawText(Cow::Borrowed(content)));
+                self.events.next();
+                node
+            }
             Some(Event::Comment(content)) => {
                 let node = Ok(Node::Comment(Cow::Borrowed(content)));
                 self.events.next();
@@ -121,21 +192,53 @@ impl<'l, T: Iterator<Item = Event<'l>>> Parser<'l, T> {
         }
     }
 
+    fn check_limits(&self, name: &str, attributes: &Attributes) -> Result<()> {
+        match self.limits.max_depth {
+            Some(max) if self.depth >= max => {
+                raise!("found <{}> nested deeper than {} levels", name, max);
+            }
+            _ => {}
+        }
+        match self.limits.max_attributes {
+            Some(max) if attributes.len() > max => {
+                raise!("found more than {} attributes in <{}>", max, name);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn process_tag(&mut self) -> Result<GenericElement<'l>> {
         match self.events.next() {
-            Some(Event::Tag(name, Type::Empty, attributes)) => Ok(GenericElement::new_from(
-                Cow::Borrowed(name),
-                attributes.clone(),
-                Vec::new(),
-            )),
+            Some(Event::Tag(name, Type::Empty, attributes)) => {
+                self.check_limits(name, &attributes)?;
+                Ok(GenericElement::new_from(
+                    Cow::Borrowed(name),
+                    attributes,
+                    Vec::new(),
+                ))
+            }
             Some(Event::Tag(name, Type::Start, attributes)) => {
+                self.check_limits(name, &attributes)?;
+                self.depth += 1;
                 let mut children = Vec::new();
-                while !matches!(self.events.peek(), Some(Event::Tag(_, Type::End, _)) | None) {
+                loop {
+                    self.skip_dropped();
+                    if matches!(self.events.peek(), Some(Event::Tag(_, Type::End, _)) | None) {
+                        break;
+                    }
+                    match self.limits.max_children {
+                        Some(max) if children.len() >= max => {
+                            raise!("found more than {} children in <{}>", max, name);
+                        }
+                        _ => {}
+                    }
                     children.push(self.process_node()?);
                 }
+                self.depth -= 1;
                 match self.events.next() {
                     Some(Event::Tag(closing_name, Type::End, _)) if closing_name == name => Ok(
-                        GenericElement::new_from(Cow::Borrowed(name), attributes.clone(), children),
+                        GenericElement::new_from(Cow::Borrowed(name), attributes, children),
                     ),
                     Some(Event::Tag(closing_name, Type::End, _)) => {
                         raise!("expected </{}>, found </{}>", name, closing_name)
@@ -151,6 +254,20 @@ impl<'l, T: Iterator<Item = Event<'l>>> Parser<'l, T> {
         }
     }
 
+    /// Build a fragment, that is, any sequence of top-level nodes.
+    pub fn process_fragment(&mut self) -> Result<Vec<Node<'l>>> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_dropped();
+            if self.events.peek().is_none() {
+                break;
+            }
+            nodes.push(self.process_node()?);
+        }
+        Ok(nodes)
+    }
+
+    /// Build a document.
     pub fn process(&mut self) -> Result<Document<'l>> {
         let prolog = self.process_prolog()?;
         let svg = self.process_tag()?;
@@ -0,0 +1,64 @@
+//! The inline styles.
+
+/// Split the value of a `style` attribute into property–value pairs.
+///
+/// Declarations are separated by semicolons except for those enclosed in
+/// quotes or parentheses, as in `url(#a;b)`. Properties and values are
+/// trimmed, and declarations without a colon or with an empty property are
+/// skipped.
+pub fn parse_style(content: &str) -> Vec<(String, String)> {
+    let mut declarations = Vec::new();
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in content.char_indices() {
+        match (quote, c) {
+            (Some(expected), _) if c == expected => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ';') if depth == 0 => {
+                declarations.extend(declaration(&content[start..i]));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.extend(declaration(&content[start..]));
+    declarations
+}
+
+fn declaration(content: &str) -> Option<(String, String)> {
+    let (property, value) = content.split_once(':')?;
+    let property = property.trim();
+    if property.is_empty() {
+        return None;
+    }
+    Some((property.into(), value.trim().into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_style;
+
+    #[test]
+    fn style_parse() {
+        let pairs = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|&(property, value)| (property.to_string(), value.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            parse_style("fill:red; background:url(#a;b)"),
+            pairs(&[("fill", "red"), ("background", "url(#a;b)")]),
+        );
+        assert_eq!(
+            parse_style(" font-family: 'a;b', serif ;; stroke : none; "),
+            pairs(&[("font-family", "'a;b', serif"), ("stroke", "none")]),
+        );
+        assert!(parse_style("").is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+//! The timing of animations.
+
+use std::fmt;
+
+use crate::node::Value;
+
+/// A [timing](https://www.w3.org/TR/SVG11/animate.html#TimingAttributes) of
+/// an animation, as used in the `begin`, `dur`, and `end` attributes.
+///
+/// Durations are given in seconds, so that `Timing::seconds(2.0)` is written
+/// as `2s` and `Timing::event("click").offset(0.5)` as `click+0.5s`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timing {
+    base: Option<String>,
+    offset: Option<f64>,
+}
+
+impl Timing {
+    /// Create a clock value.
+    #[inline]
+    pub fn seconds(seconds: f64) -> Self {
+        Timing {
+            base: None,
+            offset: Some(seconds),
+        }
+    }
+
+    /// Create a timing relative to an event, such as `click`, or to another
+    /// animation, such as `intro.end`.
+    #[inline]
+    pub fn event<T: Into<String>>(event: T) -> Self {
+        Timing {
+            base: Some(event.into()),
+            offset: None,
+        }
+    }
+
+    /// Create the `indefinite` timing.
+    #[inline]
+    pub fn indefinite() -> Self {
+        Timing::event("indefinite")
+    }
+
+    /// Set an offset in seconds, which may be negative.
+    #[inline]
+    pub fn offset(mut self, seconds: f64) -> Self {
+        self.offset = Some(seconds);
+        self
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.base, self.offset) {
+            (Some(base), Some(offset)) if offset < 0.0 => {
+                write!(formatter, "{}-{}s", base, -offset)
+            }
+            (Some(base), Some(offset)) => write!(formatter, "{}+{}s", base, offset),
+            (Some(base), None) => base.fmt(formatter),
+            (None, offset) => write!(formatter, "{}s", offset.unwrap_or(0.0)),
+        }
+    }
+}
+
+impl From<Timing> for Value {
+    #[inline]
+    fn from(timing: Timing) -> Self {
+        timing.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timing;
+    use crate::node::element::Animate;
+
+    #[test]
+    fn timing_display() {
+        assert_eq!(Timing::seconds(2.0).to_string(), "2s");
+        assert_eq!(Timing::event("click").to_string(), "click");
+        assert_eq!(Timing::event("click").offset(0.5).to_string(), "click+0.5s");
+        assert_eq!(
+            Timing::event("intro.end").offset(-1.0).to_string(),
+            "intro.end-1s"
+        );
+        assert_eq!(Timing::indefinite().to_string(), "indefinite");
+
+        let animate = Animate::new()
+            .set("dur", Timing::seconds(2.0))
+            .set("begin", Timing::event("click").offset(0.5));
+        assert_eq!(
+            animate.to_string(),
+            r#"<animate begin="click+0.5s" dur="2s"/>"#
+        );
+    }
+}
@@ -0,0 +1,448 @@
+//! The transformations.
+
+#![allow(clippy::should_implement_trait)]
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::events::parser::{Error, Reader, Result};
+use crate::node::element::path::{parameters, Command, Data, Parameters, Position};
+use crate::node::Value;
+
+/// A [transformation list](https://www.w3.org/TR/SVG/coords.html#TransformProperty).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transform(Vec<TransformOp>);
+
+/// An operation of a transformation list.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransformOp {
+    /// `matrix(a, b, c, d, e, f)`.
+    Matrix(f64, f64, f64, f64, f64, f64),
+    /// `translate(tx, ty)`.
+    Translate(f64, f64),
+    /// `scale(sx, sy)`.
+    Scale(f64, f64),
+    /// `rotate(angle, cx, cy)`.
+    Rotate(f64, f64, f64),
+    /// `skewX(angle)`.
+    SkewX(f64),
+    /// `skewY(angle)`.
+    SkewY(f64),
+}
+
+struct Parser<'l> {
+    reader: Reader<'l>,
+}
+
+/// Parse the value of a `transform` attribute into its operations.
+///
+/// Operations are separated by whitespace, commas, or both, and so are their
+/// arguments. It is the same as `Transform::parse` followed by taking the
+/// operations out.
+#[inline]
+pub fn parse_transform(content: &str) -> Result<Vec<TransformOp>> {
+    Transform::parse(content).map(Vec::from)
+}
+
+impl Transform {
+    /// Create a transformation list.
+    #[inline]
+    pub fn new() -> Self {
+        Transform(Vec::new())
+    }
+
+    /// Parse a transformation list.
+    #[inline]
+    pub fn parse(content: &str) -> Result<Self> {
+        Parser::new(content).process()
+    }
+
+    /// Append an operation.
+    #[inline]
+    pub fn add(mut self, operation: TransformOp) -> Self {
+        self.0.push(operation);
+        self
+    }
+
+    /// Append a matrix.
+    #[inline]
+    pub fn matrix(self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        self.add(TransformOp::Matrix(a, b, c, d, e, f))
+    }
+
+    /// Append a translation.
+    #[inline]
+    pub fn translate(self, x: f64, y: f64) -> Self {
+        self.add(TransformOp::Translate(x, y))
+    }
+
+    /// Append a scaling.
+    #[inline]
+    pub fn scale(self, x: f64, y: f64) -> Self {
+        self.add(TransformOp::Scale(x, y))
+    }
+
+    /// Append a rotation about the origin.
+    #[inline]
+    pub fn rotate(self, angle: f64) -> Self {
+        self.add(TransformOp::Rotate(angle, 0.0, 0.0))
+    }
+
+    /// Append a rotation about a point.
+    #[inline]
+    pub fn rotate_around(self, angle: f64, x: f64, y: f64) -> Self {
+        self.add(TransformOp::Rotate(angle, x, y))
+    }
+
+    /// Append a skewing along the x axis.
+    #[inline]
+    pub fn skew_x(self, angle: f64) -> Self {
+        self.add(TransformOp::SkewX(angle))
+    }
+
+    /// Append a skewing along the y axis.
+    #[inline]
+    pub fn skew_y(self, angle: f64) -> Self {
+        self.add(TransformOp::SkewY(angle))
+    }
+
+    /// Return the operations.
+    #[inline]
+    pub fn operations(&self) -> &[TransformOp] {
+        &self.0
+    }
+
+    /// Combine the operations into a matrix `[a, b, c, d, e, f]`, as in
+    /// `matrix(a, b, c, d, e, f)`.
+    pub fn to_matrix(&self) -> [f64; 6] {
+        self.0.iter().fold(IDENTITY, |matrix, operation| {
+            multiply(matrix, operation.to_matrix())
+        })
+    }
+
+    /// Compose with another transformation list applied after this one.
+    pub fn then(self, other: Transform) -> Self {
+        let mut operations = other.0;
+        operations.extend(self.0);
+        Transform(operations)
+    }
+
+    /// Transform the coordinates of a path.
+    ///
+    /// The result has absolute positioning. Horizontal and vertical lines are
+    /// replaced with `Command::Line` commands, and elliptical arcs with cubic
+    /// Bézier curves, as neither survives a transformation in general. The
+    /// parameters of incomplete arcs, which have no meaning, are kept as they
+    /// are.
+    pub fn apply_to(&self, data: &Data) -> Data {
+        let [a, b, c, d, e, f] = self.to_matrix();
+        let map = |(x, y): (f64, f64)| vec![a * x + c * y + e, b * x + d * y + f];
+        let mut current = (0.0, 0.0);
+        let mut start = (0.0, 0.0);
+        let mut commands = Vec::with_capacity(data.len());
+        for command in data.arcs_to_cubics().absolute().iter() {
+            use Command::*;
+
+            let parameters = match (command, parameters(command)) {
+                (EllipticalArc(..), _) => {
+                    commands.push(command.clone());
+                    continue;
+                }
+                (_, Some(parameters)) => parameters,
+                (_, None) => {
+                    current = start;
+                    commands.push(Close);
+                    continue;
+                }
+            };
+            let points = match command {
+                HorizontalLine(..) => parameters
+                    .iter()
+                    .map(|&x| (x as f64, current.1))
+                    .collect::<Vec<_>>(),
+                VerticalLine(..) => parameters
+                    .iter()
+                    .map(|&y| (current.0, y as f64))
+                    .collect::<Vec<_>>(),
+                _ => parameters
+                    .chunks_exact(2)
+                    .map(|pair| (pair[0] as f64, pair[1] as f64))
+                    .collect::<Vec<_>>(),
+            };
+            if let (Move(..), Some(&first)) = (command, points.first()) {
+                start = first;
+            }
+            current = points.last().copied().unwrap_or(current);
+            let parameters = Parameters::from(points.into_iter().flat_map(map).collect::<Vec<_>>());
+            commands.push(match command {
+                Move(..) => Move(Position::Absolute, parameters),
+                Line(..) | HorizontalLine(..) | VerticalLine(..) => {
+                    Line(Position::Absolute, parameters)
+                }
+                QuadraticCurve(..) => QuadraticCurve(Position::Absolute, parameters),
+                SmoothQuadraticCurve(..) => SmoothQuadraticCurve(Position::Absolute, parameters),
+                CubicCurve(..) => CubicCurve(Position::Absolute, parameters),
+                SmoothCubicCurve(..) => SmoothCubicCurve(Position::Absolute, parameters),
+                EllipticalArc(..) | Close => unreachable!(),
+            });
+        }
+        commands.into()
+    }
+}
+
+impl TransformOp {
+    /// Return the matrix `[a, b, c, d, e, f]` of the operation, as in
+    /// `matrix(a, b, c, d, e, f)`.
+    pub fn to_matrix(&self) -> [f64; 6] {
+        match *self {
+            TransformOp::Matrix(a, b, c, d, e, f) => [a, b, c, d, e, f],
+            TransformOp::Translate(x, y) => [1.0, 0.0, 0.0, 1.0, x, y],
+            TransformOp::Scale(x, y) => [x, 0.0, 0.0, y, 0.0, 0.0],
+            TransformOp::Rotate(angle, x, y) => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let rotation = [cos, sin, -sin, cos, 0.0, 0.0];
+                let rotation = multiply([1.0, 0.0, 0.0, 1.0, x, y], rotation);
+                multiply(rotation, [1.0, 0.0, 0.0, 1.0, -x, -y])
+            }
+            TransformOp::SkewX(angle) => [1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0],
+            TransformOp::SkewY(angle) => [1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+        }
+    }
+}
+
+const IDENTITY: [f64; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+// The product of two matrices, which applies the second one first.
+fn multiply([a1, b1, c1, d1, e1, f1]: [f64; 6], [a2, b2, c2, d2, e2, f2]: [f64; 6]) -> [f64; 6] {
+    [
+        a1 * a2 + c1 * b2,
+        b1 * a2 + d1 * b2,
+        a1 * c2 + c1 * d2,
+        b1 * c2 + d1 * d2,
+        a1 * e2 + c1 * f2 + e1,
+        b1 * e2 + d1 * f2 + f1,
+    ]
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, operation) in self.0.iter().enumerate() {
+            if i > 0 {
+                " ".fmt(formatter)?;
+            }
+            operation.fmt(formatter)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TransformOp {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransformOp::Matrix(a, b, c, d, e, f) => {
+                write!(formatter, "matrix({} {} {} {} {} {})", a, b, c, d, e, f)
+            }
+            TransformOp::Translate(x, y) => write!(formatter, "translate({} {})", x, y),
+            TransformOp::Scale(x, y) => write!(formatter, "scale({} {})", x, y),
+            TransformOp::Rotate(angle, x, y) if x == 0.0 && y == 0.0 => {
+                write!(formatter, "rotate({})", angle)
+            }
+            TransformOp::Rotate(angle, x, y) => {
+                write!(formatter, "rotate({} {} {})", angle, x, y)
+            }
+            TransformOp::SkewX(angle) => write!(formatter, "skewX({})", angle),
+            TransformOp::SkewY(angle) => write!(formatter, "skewY({})", angle),
+        }
+    }
+}
+
+impl FromStr for Transform {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(content: &str) -> Result<Self> {
+        Transform::parse(content)
+    }
+}
+
+impl From<Vec<TransformOp>> for Transform {
+    #[inline]
+    fn from(operations: Vec<TransformOp>) -> Self {
+        Transform(operations)
+    }
+}
+
+impl From<Transform> for Vec<TransformOp> {
+    #[inline]
+    fn from(Transform(operations): Transform) -> Self {
+        operations
+    }
+}
+
+impl From<Transform> for Value {
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        transform.to_string().into()
+    }
+}
+
+macro_rules! raise(
+    ($parser:expr, $($argument:tt)*) => (
+        return Err(Error::new($parser.reader.position(), format!($($argument)*)))
+    );
+);
+
+impl<'l> Parser<'l> {
+    #[inline]
+    fn new(content: &'l str) -> Self {
+        Parser {
+            reader: Reader::new(content),
+        }
+    }
+
+    fn process(&mut self) -> Result<Transform> {
+        let mut operations = Vec::new();
+        loop {
+            self.reader.consume_whitespace();
+            self.reader.consume_char(',');
+            self.reader.consume_whitespace();
+            match self.read_operation()? {
+                Some(operation) => operations.push(operation),
+                _ => break,
+            }
+        }
+        if !self.reader.is_done() {
+            raise!(self, "found an unexpected ending of a transformation list");
+        }
+        Ok(Transform(operations))
+    }
+
+    fn read_operation(&mut self) -> Result<Option<TransformOp>> {
+        let name = match self
+            .reader
+            .capture(|reader| reader.consume_while(|c| c.is_ascii_alphabetic()))
+        {
+            Some(name) => name,
+            _ => return Ok(None),
+        };
+        self.reader.consume_whitespace();
+        if !self.reader.consume_char('(') {
+            raise!(self, "expected '(' after '{}'", name);
+        }
+        let arguments = self.read_arguments()?;
+        if !self.reader.consume_char(')') {
+            raise!(self, "expected ')' after the arguments of '{}'", name);
+        }
+        let operation = match (name, &arguments[..]) {
+            ("matrix", &[a, b, c, d, e, f]) => TransformOp::Matrix(a, b, c, d, e, f),
+            ("translate", &[x]) => TransformOp::Translate(x, 0.0),
+            ("translate", &[x, y]) => TransformOp::Translate(x, y),
+            ("scale", &[x]) => TransformOp::Scale(x, x),
+            ("scale", &[x, y]) => TransformOp::Scale(x, y),
+            ("rotate", &[angle]) => TransformOp::Rotate(angle, 0.0, 0.0),
+            ("rotate", &[angle, x, y]) => TransformOp::Rotate(angle, x, y),
+            ("skewX", &[angle]) => TransformOp::SkewX(angle),
+            ("skewY", &[angle]) => TransformOp::SkewY(angle),
+            ("matrix" | "translate" | "scale" | "rotate" | "skewX" | "skewY", _) => {
+                raise!(self, "found a wrong number of arguments of '{}'", name)
+            }
+            _ => raise!(self, "found an unknown transformation '{}'", name),
+        };
+        Ok(Some(operation))
+    }
+
+    fn read_arguments(&mut self) -> Result<Vec<f64>> {
+        let mut arguments = Vec::new();
+        loop {
+            self.reader.consume_whitespace();
+            let number = match self.reader.capture(|reader| reader.consume_number()) {
+                Some(number) => number,
+                _ => break,
+            };
+            match number.parse() {
+                Ok(number) => arguments.push(number),
+                _ => raise!(self, "failed to parse a number '{}'", number),
+            }
+            self.reader.consume_whitespace();
+            self.reader.consume_char(',');
+        }
+        Ok(arguments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_transform, Transform, TransformOp};
+    use crate::node::element::path::Data;
+
+    #[test]
+    fn transform_apply_to() {
+        let transform = Transform::new()
+            .scale(2.0, 3.0)
+            .then(Transform::new().translate(10.0, 20.0));
+        assert_eq!(transform.to_string(), "translate(10 20) scale(2 3)");
+        assert_eq!(transform.to_matrix(), [2.0, 0.0, 0.0, 3.0, 10.0, 20.0]);
+
+        let square = Data::parse("M0,0 H1 V1 h-1 z m2,2 l1,0").unwrap();
+        assert_eq!(
+            transform.apply_to(&square).to_string(),
+            "M10,20 L12,20 L12,23 L10,23 z M14,26 L16,26",
+        );
+
+        let arc = Data::parse("M0,0 A5,5 0 0 1 L10,10").unwrap();
+        assert_eq!(
+            Transform::new()
+                .translate(1.0, 1.0)
+                .apply_to(&arc)
+                .to_string(),
+            "M1,1 A5,5,0,0,1 L11,11",
+        );
+
+        let rotation = Transform::new().rotate_around(90.0, 1.0, 1.0).to_matrix();
+        let expected = [0.0, 1.0, -1.0, 0.0, 2.0, 0.0];
+        for (value, expected) in rotation.iter().zip(expected.iter()) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn transform_parse_operations() {
+        assert_eq!(
+            parse_transform("translate(10,20) rotate(45 5 5)").unwrap(),
+            vec![
+                TransformOp::Translate(10.0, 20.0),
+                TransformOp::Rotate(45.0, 5.0, 5.0),
+            ],
+        );
+        assert_eq!(parse_transform("").unwrap(), vec![]);
+        assert!(parse_transform("rotate(45 5)").is_err());
+    }
+
+    #[test]
+    fn transform_parse() {
+        let transform = "translate(10,20) rotate(45 5 5), scale(2)"
+            .parse::<Transform>()
+            .unwrap();
+
+        assert_eq!(
+            transform.operations(),
+            &[
+                TransformOp::Translate(10.0, 20.0),
+                TransformOp::Rotate(45.0, 5.0, 5.0),
+                TransformOp::Scale(2.0, 2.0),
+            ],
+        );
+        assert_eq!(
+            transform.to_string(),
+            "translate(10 20) rotate(45 5 5) scale(2 2)",
+        );
+        assert_eq!(
+            Transform::new().translate(1.0, 2.0).skew_x(30.0),
+            "translate(1 2) skewX(30)".parse().unwrap(),
+        );
+
+        assert!("rotate(1, 2)".parse::<Transform>().is_err());
+        assert!("shear(1)".parse::<Transform>().is_err());
+        assert!("scale(1".parse::<Transform>().is_err());
+    }
+}
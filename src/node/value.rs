@@ -1,56 +1,320 @@
+use std::convert::TryFrom;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
 /// A value of an attribute.
-#[derive(Clone, Debug, Hash, Eq, PartialEq)]
-pub struct Value(String);
+///
+/// Two values are equal when their contents are equal; the quote style, the
+/// surrounding whitespace, and whether the value is raw are formatting hints
+/// and do not take part in comparisons.
+#[derive(Clone, Debug)]
+pub struct Value {
+    inner: String,
+    kind: Kind,
+    quote: Option<Quote>,
+    spacing: Option<Box<Spacing>>,
+    raw: bool,
+}
+
+/// The whitespace around an attribute and its position among the attributes
+/// of the tag it was read from.
+#[derive(Clone, Debug)]
+struct Spacing {
+    before: String,
+    after: String,
+    order: usize,
+}
+
+/// The type of the content a value was created from.
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    Integer(i64),
+    Float(f64),
+    Text,
+    List,
+    Flag,
+}
+
+/// A quote character delimiting a value of an attribute.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Quote {
+    /// A single quote (`'`).
+    Single,
+    /// A double quote (`"`).
+    Double,
+}
+
+impl Value {
+    /// Create a value that is written out verbatim, without escaping.
+    ///
+    /// This is meant for content that is already escaped, such as values with
+    /// entity references managed by the caller. Nothing is checked, so a raw
+    /// value containing `<`, a bare `&`, or the delimiting quote character
+    /// results in malformed output.
+    #[inline]
+    pub fn raw<T: Into<String>>(inner: T) -> Self {
+        Value {
+            inner: inner.into(),
+            kind: Kind::Text,
+            quote: None,
+            spacing: None,
+            raw: true,
+        }
+    }
+
+    /// Create a value for a presence-only attribute, which is the name of the
+    /// attribute itself.
+    #[inline]
+    pub(crate) fn flag<T: Into<String>>(name: T) -> Self {
+        Value {
+            kind: Kind::Flag,
+            ..Value::from(name.into())
+        }
+    }
+
+    /// Check if the value is for a presence-only attribute.
+    #[inline]
+    pub fn is_flag(&self) -> bool {
+        matches!(self.kind, Kind::Flag)
+    }
+
+    /// Create a value from a number limited to `[min, max]`.
+    ///
+    /// A number that is not a number is replaced with `min`.
+    #[inline]
+    pub fn clamped_f64(number: f64, min: f64, max: f64) -> Self {
+        if number.is_nan() {
+            return Value::from(min);
+        }
+        Value::from(number.clamp(min, max))
+    }
+
+    /// Create a value for an opacity, which is limited to `[0, 1]`.
+    #[inline]
+    pub fn opacity(number: f64) -> Self {
+        Value::clamped_f64(number, 0.0, 1.0)
+    }
+
+    /// Return the value as an integer.
+    ///
+    /// A value created from an integer is returned as is, and one created from
+    /// a float is returned only if it has no fractional part. Other values are
+    /// parsed.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.kind {
+            Kind::Integer(value) => Some(value),
+            Kind::Float(value) if value.fract() == 0.0 && value.abs() < 9.2e18 => {
+                Some(value as i64)
+            }
+            Kind::Float(_) | Kind::List | Kind::Flag => None,
+            Kind::Text => self.inner.trim().parse().ok(),
+        }
+    }
+
+    /// Return the value as a float.
+    ///
+    /// A value created from a number is returned as is, and other values are
+    /// parsed.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.kind {
+            Kind::Integer(value) => Some(value as f64),
+            Kind::Float(value) => Some(value),
+            Kind::List | Kind::Flag => None,
+            Kind::Text => self.inner.trim().parse().ok(),
+        }
+    }
+
+    /// Return the value with its content in ASCII lowercase, keeping the
+    /// quote style and spacing.
+    #[inline]
+    pub(crate) fn to_ascii_lowercase(&self) -> Self {
+        Value {
+            inner: self.inner.to_ascii_lowercase(),
+            ..self.clone()
+        }
+    }
+
+    /// Check if the value is written out verbatim.
+    #[inline]
+    pub fn is_raw(&self) -> bool {
+        self.raw
+    }
+
+    /// Return the quote style the value was written with, if known.
+    #[inline]
+    pub fn quote(&self) -> Option<Quote> {
+        self.quote
+    }
+
+    /// Set the quote style to use when writing the value.
+    #[inline]
+    pub fn with_quote(mut self, quote: Quote) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+
+    /// Return the whitespace the attribute was written with before its name
+    /// and after its value, if known.
+    #[inline]
+    pub fn spacing(&self) -> Option<(&str, &str)> {
+        self.spacing
+            .as_ref()
+            .map(|spacing| (spacing.before.as_str(), spacing.after.as_str()))
+    }
+
+    /// Set the whitespace to write before the name of the attribute and after
+    /// its value instead of a single space before the name.
+    ///
+    /// # Panics
+    ///
+    /// The function panics unless `before` is nonempty and both consist only
+    /// of [whitespace](https://www.w3.org/TR/REC-xml/#NT-S).
+    pub fn with_spacing<T, U>(self, before: T, after: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        let (before, after) = (before.into(), after.into());
+        let whitespace = |content: &str| {
+            content
+                .chars()
+                .all(|c| matches!(c, ' ' | '\t' | '\n' | '\r'))
+        };
+        assert!(
+            !before.is_empty() && whitespace(&before) && whitespace(&after),
+            "expected whitespace around an attribute",
+        );
+        let order = self.order();
+        self.with_source_spacing(before, after, order)
+    }
+
+    /// Set the whitespace as read from a tag, where the attribute is at the
+    /// given position.
+    pub(crate) fn with_source_spacing<T, U>(mut self, before: T, after: U, order: usize) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.spacing = Some(Box::new(Spacing {
+            before: before.into(),
+            after: after.into(),
+            order,
+        }));
+        self
+    }
+
+    /// Return the position of the attribute in the tag it was read from, or
+    /// `usize::MAX` if unknown.
+    #[inline]
+    pub(crate) fn order(&self) -> usize {
+        self.spacing
+            .as_ref()
+            .map_or(usize::MAX, |spacing| spacing.order)
+    }
+}
+
+impl Quote {
+    /// Return the quote character.
+    #[inline]
+    pub fn as_char(self) -> char {
+        match self {
+            Quote::Single => '\'',
+            Quote::Double => '"',
+        }
+    }
+}
 
 impl Deref for Value {
     type Target = str;
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.inner
     }
 }
 
 impl fmt::Display for Value {
     #[inline]
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        self.0.fmt(formatter)
+        self.inner.fmt(formatter)
+    }
+}
+
+impl PartialEq for Value {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
     }
 }
 
 impl From<Value> for String {
     #[inline]
-    fn from(Value(inner): Value) -> Self {
-        inner
+    fn from(value: Value) -> Self {
+        value.inner
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(inner: String) -> Self {
+        Value {
+            inner,
+            kind: Kind::Text,
+            quote: None,
+            spacing: None,
+            raw: false,
+        }
     }
 }
 
 macro_rules! implement {
-    ($($primitive:ty,)*) => (
+    ($($primitive:ty => $kind:expr,)*) => (
         $(impl From<$primitive> for Value {
             #[inline]
             fn from(inner: $primitive) -> Self {
-                Value(inner.to_string())
+                #[allow(clippy::redundant_closure_call)]
+                let kind = $kind(inner);
+                Value {
+                    kind,
+                    ..Value::from(inner.to_string())
+                }
             }
         })*
     );
 }
 
+// Floats are written in the shortest form that reads back as the same number,
+// so `10.0` is written as `10` and `0.1f32` as `0.1`.
 implement! {
-    i8, i16, i32, i64, isize,
-    u8, u16, u32, u64, usize,
-    f32, f64,
-    String,
-    bool,
+    i8 => |inner| Kind::Integer(inner as i64),
+    i16 => |inner| Kind::Integer(inner as i64),
+    i32 => |inner| Kind::Integer(inner as i64),
+    i64 => Kind::Integer,
+    isize => |inner| Kind::Integer(inner as i64),
+    u8 => |inner| Kind::Integer(inner as i64),
+    u16 => |inner| Kind::Integer(inner as i64),
+    u32 => |inner| Kind::Integer(inner as i64),
+    u64 => |inner| i64::try_from(inner).map_or(Kind::Text, Kind::Integer),
+    usize => |inner| i64::try_from(inner).map_or(Kind::Text, Kind::Integer),
+    f32 => |inner| Kind::Float(inner as f64),
+    f64 => Kind::Float,
+    bool => |_| Kind::Text,
 }
 
 impl<'l> From<&'l str> for Value {
     #[inline]
     fn from(inner: &'l str) -> Value {
-        Value(inner.to_string())
+        inner.to_string().into()
     }
 }
 
@@ -59,13 +323,15 @@ where
     T: Into<Value>,
 {
     fn from(mut inner: Vec<T>) -> Self {
-        Value(
-            inner
+        Value {
+            kind: Kind::List,
+            ..inner
                 .drain(..)
-                .map(|value| value.into().0)
+                .map(|value| value.into().inner)
                 .collect::<Vec<_>>()
-                .join(" "),
-        )
+                .join(" ")
+                .into()
+        }
     }
 }
 
@@ -77,7 +343,10 @@ macro_rules! implement {
             $($t: Into<Value>),*
         {
             fn from(inner: ($($t),*)) -> Self {
-                Value(format!($pattern, $(implement!(@express inner.$n).into()),*))
+                Value {
+                    kind: Kind::List,
+                    ..format!($pattern, $(implement!(@express inner.$n).into()),*).into()
+                }
             }
         }
     );
@@ -93,5 +362,46 @@ mod tests {
     #[test]
     fn value_from_vector() {
         assert_eq!(String::from(Value::from(vec![42, 69])), "42 69");
+        assert_eq!(Value::from(vec![42, 69]).as_i64(), None);
+    }
+
+    #[test]
+    fn value_clamped() {
+        assert_eq!(&*Value::opacity(1.5), "1");
+        assert_eq!(&*Value::opacity(-0.2), "0");
+        assert_eq!(&*Value::opacity(0.25), "0.25");
+        assert_eq!(&*Value::opacity(f64::NAN), "0");
+        assert_eq!(&*Value::clamped_f64(120.0, -90.0, 90.0), "90");
+    }
+
+    #[test]
+    fn value_from_number() {
+        assert_eq!(&*Value::from(10), "10");
+        assert_eq!(Value::from(10).as_i64(), Some(10));
+        assert_eq!(&*Value::from(i64::MAX), "9223372036854775807");
+        assert_eq!(Value::from(i64::MAX).as_i64(), Some(i64::MAX));
+
+        assert_eq!(&*Value::from(10.0), "10");
+        assert_eq!(Value::from(10.0).as_i64(), Some(10));
+        assert_eq!(&*Value::from(0.1f32), "0.1");
+        assert_eq!(Value::from(0.1f32).as_f64(), Some(0.1f32 as f64));
+        assert_eq!(&*Value::from(2.5), "2.5");
+        assert_eq!(Value::from(2.5).as_i64(), None);
+        assert_eq!(Value::from(2.5).as_f64(), Some(2.5));
+
+        assert_eq!(Value::from(" 42 ").as_i64(), Some(42));
+        assert_eq!(Value::from("10px").as_f64(), None);
+    }
+
+    #[test]
+    fn value_with_spacing() {
+        let value = Value::from(1).with_spacing("\n  ", " ");
+        assert_eq!(value.spacing(), Some(("\n  ", " ")));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected whitespace")]
+    fn value_with_spacing_invalid() {
+        let _ = Value::from(1).with_spacing("", "");
     }
 }
@@ -0,0 +1,229 @@
+//! The viewport mapping.
+
+use crate::node::element::path::Data;
+use crate::node::element::{tag, GenericElement};
+use crate::node::{
+    Document, Element, Length, MeetOrSlice, Node, PreserveAspectRatio, Transform, Unit,
+};
+
+/// A mapping between the pixels of a viewport and the user units of a
+/// `viewBox`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mapping {
+    scale: (f64, f64),
+    translation: (f64, f64),
+}
+
+impl Mapping {
+    /// Map a point from pixels to user units.
+    #[inline]
+    pub fn to_user(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            (x - self.translation.0) / self.scale.0,
+            (y - self.translation.1) / self.scale.1,
+        )
+    }
+
+    /// Map a point from user units to pixels.
+    #[inline]
+    pub fn to_pixel(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            x * self.scale.0 + self.translation.0,
+            y * self.scale.1 + self.translation.1,
+        )
+    }
+}
+
+impl From<Mapping> for Transform {
+    #[inline]
+    fn from(mapping: Mapping) -> Self {
+        Transform::new()
+            .translate(mapping.translation.0, mapping.translation.1)
+            .scale(mapping.scale.0, mapping.scale.1)
+    }
+}
+
+/// Elements whose content is not rendered where it appears.
+const UNRENDERED: &[&str] = &[
+    tag::ClipPath,
+    tag::Definitions,
+    tag::Marker,
+    tag::Mask,
+    tag::Pattern,
+    tag::Symbol,
+];
+
+impl<'l> Document<'l> {
+    /// Set the `viewBox` attribute of the root element to the bounding box of
+    /// the drawn shapes extended by `padding` on every side.
+    ///
+    /// Paths and basic shapes are taken into account, while transforms,
+    /// strokes, and the content of `defs` and similar elements are not. If
+    /// nothing is drawn, the document is left as it is.
+    pub fn fit_view_box(&mut self, padding: f64) {
+        let mut bounds = None;
+        extend_bounds(self.get_svg(), &mut bounds);
+        if let Some((x1, y1, x2, y2)) = bounds {
+            self.get_mut_svg().assign(
+                "viewBox",
+                (
+                    x1 - padding,
+                    y1 - padding,
+                    x2 - x1 + 2.0 * padding,
+                    y2 - y1 + 2.0 * padding,
+                ),
+            );
+        }
+    }
+
+    /// Compute the mapping from the `viewBox`, `width`, `height`, and
+    /// `preserveAspectRatio` attributes of the root element.
+    ///
+    /// If `width` or `height` is absent, the corresponding dimension of the
+    /// `viewBox` is used. `None` is returned if there is no valid `viewBox` or
+    /// if `width` or `height` is not given in user units or pixels.
+    pub fn viewport_mapping(&self) -> Option<Mapping> {
+        let attributes = self.get_svg().get_attributes();
+        let view_box = attributes
+            .get("viewBox")?
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|number| !number.is_empty())
+            .map(|number| number.parse::<f64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let (x, y, width, height) = match view_box[..] {
+            [x, y, width, height] if width > 0.0 && height > 0.0 => (x, y, width, height),
+            _ => return None,
+        };
+        let dimension = |name: &str, default: f64| match attributes.get(name) {
+            Some(value) => match Length::parse(value) {
+                Ok(Length {
+                    number,
+                    unit: Unit::None | Unit::Px,
+                }) => Some(number),
+                _ => None,
+            },
+            _ => Some(default),
+        };
+        let viewport = (dimension("width", width)?, dimension("height", height)?);
+        let aspect = match attributes.get("preserveAspectRatio") {
+            Some(value) => PreserveAspectRatio::parse(value).ok()?,
+            _ => PreserveAspectRatio::default(),
+        };
+        let scale = (viewport.0 / width, viewport.1 / height);
+        let factors = match aspect.align.factors() {
+            Some(factors) => factors,
+            _ => {
+                return Some(Mapping {
+                    scale,
+                    translation: (-x * scale.0, -y * scale.1),
+                })
+            }
+        };
+        let scale = if aspect.meet_or_slice == MeetOrSlice::Slice {
+            scale.0.max(scale.1)
+        } else {
+            scale.0.min(scale.1)
+        };
+        Some(Mapping {
+            scale: (scale, scale),
+            translation: (
+                -x * scale + factors.0 * (viewport.0 - width * scale),
+                -y * scale + factors.1 * (viewport.1 - height * scale),
+            ),
+        })
+    }
+}
+
+// The bounds are given by the corners `(x1, y1, x2, y2)`.
+fn extend_bounds(element: &GenericElement, bounds: &mut Option<(f64, f64, f64, f64)>) {
+    let path = match element.get_name() {
+        tag::Path => Some(element.clone()),
+        _ => element.to_path().map(GenericElement::from),
+    };
+    let data = path.and_then(|path| path.get_attributes().get("d")?.parse().ok());
+    if let Some((x, y, width, height)) = data.and_then(|data: Data| data.bounding_box()) {
+        let (x1, y1, x2, y2) = bounds.get_or_insert((x, y, x + width, y + height));
+        *x1 = x1.min(x);
+        *y1 = y1.min(y);
+        *x2 = x2.max(x + width);
+        *y2 = y2.max(y + height);
+    }
+    if UNRENDERED.contains(&element.get_name()) {
+        return;
+    }
+    for child in element.get_children() {
+        if let Node::Element(child) = child {
+            extend_bounds(child, bounds);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::node::element::{Definitions, Rectangle};
+    use crate::node::Transform;
+    use crate::Document;
+
+    #[test]
+    fn viewport_mapping() {
+        let document = Document::new()
+            .set("width", 100)
+            .set("height", 100)
+            .set("viewBox", (0, 0, 50, 50));
+        let mapping = document.viewport_mapping().unwrap();
+
+        assert_eq!(mapping.to_user((50.0, 50.0)), (25.0, 25.0));
+        assert_eq!(mapping.to_pixel((25.0, 25.0)), (50.0, 50.0));
+        assert_eq!(
+            Transform::from(mapping).to_string(),
+            "translate(0 0) scale(2 2)",
+        );
+
+        let document = Document::new()
+            .set("width", 200)
+            .set("height", 100)
+            .set("viewBox", (0, 0, 50, 50));
+        let mapping = document.viewport_mapping().unwrap();
+        assert_eq!(mapping.to_pixel((0.0, 0.0)), (50.0, 0.0));
+
+        let document = document.set("preserveAspectRatio", "xMinYMin slice");
+        let mapping = document.viewport_mapping().unwrap();
+        assert_eq!(mapping.to_pixel((50.0, 50.0)), (200.0, 200.0));
+
+        let document = document.set("preserveAspectRatio", "none");
+        let mapping = document.viewport_mapping().unwrap();
+        assert_eq!(mapping.to_pixel((50.0, 50.0)), (200.0, 100.0));
+
+        assert!(Document::new().viewport_mapping().is_none());
+    }
+
+    #[test]
+    fn fit_view_box() {
+        let mut document = Document::new()
+            .add(
+                Rectangle::new()
+                    .set("x", 10)
+                    .set("y", 20)
+                    .set("width", 30)
+                    .set("height", 40),
+            )
+            .add(
+                Rectangle::new()
+                    .set("x", -5)
+                    .set("y", 50)
+                    .set("width", 10)
+                    .set("height", 20),
+            )
+            .add(Definitions::new().add(Rectangle::new().set("width", 1000).set("height", 1000)));
+        document.fit_view_box(5.0);
+
+        assert_eq!(
+            &**document.get_svg().get_attributes().get("viewBox").unwrap(),
+            "-10 15 55 60",
+        );
+
+        let mut document = Document::new();
+        document.fit_view_box(5.0);
+        assert!(document.get_svg().get_attributes().get("viewBox").is_none());
+    }
+}
@@ -0,0 +1,145 @@
+//! The XML declarations.
+
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::events::parser::{Error, Result};
+use crate::node::{Instruction, Node};
+
+/// An [XML declaration](https://www.w3.org/TR/REC-xml/#sec-prolog-dtd).
+///
+/// The content corresponds to that of the `<?xml ... ?>` instruction without
+/// the delimiters.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct XmlDeclaration {
+    version: String,
+    encoding: Option<String>,
+    standalone: Option<bool>,
+}
+
+impl XmlDeclaration {
+    /// Create a declaration.
+    #[inline]
+    pub fn new<T: Into<String>>(version: T) -> Self {
+        XmlDeclaration {
+            version: version.into(),
+            encoding: None,
+            standalone: None,
+        }
+    }
+
+    /// Parse a declaration.
+    ///
+    /// The pseudo-attributes have to appear in the order mandated by the
+    /// specification, that is, `version`, `encoding`, and `standalone`, and
+    /// only the first one is required.
+    pub fn parse(content: &str) -> Result<Self> {
+        let instruction = Instruction::parse(content)?;
+        if instruction.get_target() != "xml" {
+            return Err(Error::new((1, 1), "expected an XML declaration"));
+        }
+        let mut attributes = instruction.get_attributes().iter().peekable();
+        let mut declaration = match attributes.next() {
+            Some((name, value)) if name == "version" => XmlDeclaration::new(&**value),
+            _ => return Err(Error::new((1, 1), "expected a version")),
+        };
+        if let Some((_, value)) = attributes.next_if(|(name, _)| name == "encoding") {
+            declaration = declaration.encoding(&**value);
+        }
+        if let Some((_, value)) = attributes.next_if(|(name, _)| name == "standalone") {
+            declaration = match &**value {
+                "yes" => declaration.standalone(true),
+                "no" => declaration.standalone(false),
+                _ => return Err(Error::new((1, 1), "expected 'yes' or 'no' for standalone")),
+            };
+        }
+        if let Some((name, _)) = attributes.next() {
+            return Err(Error::new(
+                (1, 1),
+                format!("found an unexpected pseudo-attribute '{}'", name),
+            ));
+        }
+        Ok(declaration)
+    }
+
+    /// Set the encoding.
+    pub fn encoding<T: Into<String>>(mut self, encoding: T) -> Self {
+        self.encoding = Some(encoding.into());
+        self
+    }
+
+    /// Set whether the document is standalone.
+    pub fn standalone(mut self, standalone: bool) -> Self {
+        self.standalone = Some(standalone);
+        self
+    }
+
+    /// Return the version.
+    #[inline]
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    /// Return the encoding if declared.
+    #[inline]
+    pub fn get_encoding(&self) -> Option<&str> {
+        self.encoding.as_deref()
+    }
+
+    /// Return whether the document is standalone if declared.
+    #[inline]
+    pub fn get_standalone(&self) -> Option<bool> {
+        self.standalone
+    }
+}
+
+impl fmt::Display for XmlDeclaration {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "xml version=\"{}\"", self.version)?;
+        if let Some(encoding) = &self.encoding {
+            write!(formatter, " encoding=\"{}\"", encoding)?;
+        }
+        if let Some(standalone) = self.standalone {
+            let standalone = if standalone { "yes" } else { "no" };
+            write!(formatter, " standalone=\"{}\"", standalone)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'l> From<XmlDeclaration> for Node<'l> {
+    #[inline]
+    fn from(declaration: XmlDeclaration) -> Self {
+        Node::Instruction(Cow::Owned(declaration.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XmlDeclaration;
+
+    #[test]
+    fn xml_declaration_parse() {
+        let content = r#"xml version="1.0" encoding="utf-8" standalone="no""#;
+        let declaration = XmlDeclaration::parse(content).unwrap();
+
+        assert_eq!(declaration.get_version(), "1.0");
+        assert_eq!(declaration.get_encoding(), Some("utf-8"));
+        assert_eq!(declaration.get_standalone(), Some(false));
+        assert_eq!(declaration.to_string(), content);
+        assert_eq!(
+            declaration,
+            XmlDeclaration::new("1.0")
+                .encoding("utf-8")
+                .standalone(false),
+        );
+
+        let declaration = XmlDeclaration::parse(r#"xml version='1.1'"#).unwrap();
+        assert_eq!(declaration.get_encoding(), None);
+        assert_eq!(declaration.get_standalone(), None);
+
+        assert!(XmlDeclaration::parse(r#"xml encoding="utf-8""#).is_err());
+        assert!(XmlDeclaration::parse(r#"xml version="1.0" standalone="maybe""#).is_err());
+        assert!(XmlDeclaration::parse(r#"xml-stylesheet version="1.0""#).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! The scanner for custom tokenization.
+//!
+//! The scanner is the reader underlying the parser of this crate, limited to
+//! a small set of operations, which makes it possible to write parsers for
+//! SVG-adjacent formats that report positions the same way.
+//!
+//! ```
+//! use svg::scan::Scanner;
+//!
+//! let mut scanner = Scanner::new("{{ name }} rest");
+//! assert!(scanner.consume_char('{') && scanner.consume_char('{'));
+//! let name = scanner.capture(|scanner| scanner.consume_until_char('}'));
+//! assert_eq!(name, Some("name"));
+//! ```
+
+use std::str::Chars;
+
+use crate::events::parser::Reader;
+
+/// A scanner.
+pub struct Scanner<'l> {
+    content: &'l str,
+    reader: Reader<'l>,
+}
+
+impl<'l> Scanner<'l> {
+    /// Create a scanner.
+    #[inline]
+    pub fn new(content: &'l str) -> Self {
+        Scanner {
+            content,
+            reader: Reader::new(content),
+        }
+    }
+
+    /// Run a block and return the content it consumed with surrounding
+    /// whitespace trimmed.
+    ///
+    /// The result is `None` if the block returns `false` or if the trimmed
+    /// content is empty. The consumed content is not restored either way.
+    pub fn capture<F>(&mut self, block: F) -> Option<&'l str>
+    where
+        F: Fn(&mut Scanner<'l>) -> bool,
+    {
+        let start = self.reader.byte_offset();
+        if !block(self) {
+            return None;
+        }
+        let content = self.content[start..self.reader.byte_offset()].trim();
+        if content.is_empty() {
+            None
+        } else {
+            Some(content)
+        }
+    }
+
+    /// Consume a character if it is the given one.
+    #[inline]
+    pub fn consume_char(&mut self, target: char) -> bool {
+        self.reader.consume_char(target)
+    }
+
+    /// Consume characters up to but excluding the given one or up to the end.
+    #[inline]
+    pub fn consume_until_char(&mut self, target: char) -> bool {
+        self.reader.consume_until_char(target)
+    }
+
+    /// Consume [whitespace](https://www.w3.org/TR/REC-xml/#NT-S).
+    #[inline]
+    pub fn consume_whitespace(&mut self) -> bool {
+        self.reader.consume_whitespace()
+    }
+
+    /// Check if the whole content has been consumed.
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.reader.is_done()
+    }
+
+    /// Return the remaining characters without consuming them.
+    #[inline]
+    pub fn peek_many(&self) -> Chars<'l> {
+        self.reader.peek_many()
+    }
+
+    /// Return the line and the column, both starting at one, with the column
+    /// counted in characters.
+    #[inline]
+    pub fn position(&self) -> (usize, usize) {
+        self.reader.position()
+    }
+
+    /// Return the offset from the start of the content in bytes.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.reader.byte_offset()
+    }
+
+    /// Return the offset from the start of the content in characters.
+    #[inline]
+    pub fn char_offset(&self) -> usize {
+        self.reader.char_offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scanner;
+
+    #[test]
+    fn scanner_tokenize() {
+        let mut scanner = Scanner::new("{{ first }}\n{{second}}");
+        let mut names = Vec::new();
+        while !scanner.is_done() {
+            scanner.consume_whitespace();
+            assert!(scanner.peek_many().as_str().starts_with("{{"));
+            assert!(scanner.consume_char('{') && scanner.consume_char('{'));
+            let position = scanner.position();
+            let name = scanner.capture(|scanner| scanner.consume_until_char('}'));
+            assert!(scanner.consume_char('}') && scanner.consume_char('}'));
+            names.push((name.unwrap(), position));
+        }
+
+        assert_eq!(names, vec![("first", (1, 3)), ("second", (2, 3))]);
+        assert_eq!(scanner.char_offset(), 22);
+    }
+}
@@ -0,0 +1,222 @@
+//! The escaping rules.
+
+use std::borrow::Cow;
+
+use crate::events::parser::{Error, Reader, Result};
+
+/// Escape content for use in text as well as in values of attributes
+/// delimited by double quotes.
+///
+/// `<`, `>`, `&`, `"`, and `'` are all replaced with references. The content
+/// is borrowed if nothing needs escaping.
+pub fn escape(content: &str) -> Cow<'_, str> {
+    escape_with(content, |content, i| match content.as_bytes()[i] {
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        b'&' => Some("&amp;"),
+        b'"' => Some("&quot;"),
+        b'\'' => Some("&apos;"),
+        _ => None,
+    })
+}
+
+/// Replace the predefined entities and character references with the
+/// characters they stand for.
+///
+/// An error is returned for an `&` that does not start a well-formed
+/// reference to a predefined entity or a valid character. The content is
+/// borrowed if there are no references.
+pub fn unescape(content: &str) -> Result<Cow<'_, str>> {
+    let mut rest = content;
+    while let Some(i) = rest.find('&') {
+        let offset = content.len() - rest.len() + i;
+        rest = &rest[i..];
+        match reference_length(rest)
+            .filter(|&length| decode_reference(&rest[1..length - 1]).is_some())
+        {
+            Some(length) => rest = &rest[length..],
+            None => {
+                let mut reader = Reader::new(content);
+                while reader.byte_offset() < offset {
+                    reader.next();
+                }
+                let error = Error::new(reader.position(), "found a malformed reference");
+                return Err(error.with_offset(offset));
+            }
+        }
+    }
+    Ok(unescape_lenient(content))
+}
+
+/// Escape a text node.
+///
+/// Text is treated as markup that may already contain references, so `&` is
+/// escaped only when it does not start a well-formed reference. In addition,
+/// `<` is always escaped, and so is `>` when it closes the sequence `]]>`,
+/// which is not allowed to appear in text.
+pub fn escape_text(content: &str) -> Cow<'_, str> {
+    escape_with(content, |content, i| match content.as_bytes()[i] {
+        b'<' => Some("&lt;"),
+        b'&' if reference_length(&content[i..]).is_none() => Some("&amp;"),
+        b'>' if content[..i].ends_with("]]") => Some("&gt;"),
+        _ => None,
+    })
+}
+
+/// Escape a value of an attribute delimited by the given quote character.
+pub fn escape_attribute(content: &str, quote: char) -> Cow<'_, str> {
+    escape_with(content, |content, i| match content.as_bytes()[i] {
+        b'<' => Some("&lt;"),
+        b'&' => Some("&amp;"),
+        b'"' if quote == '"' => Some("&quot;"),
+        b'\'' if quote == '\'' => Some("&apos;"),
+        _ => None,
+    })
+}
+
+/// Replace the predefined entities and character references with the
+/// characters they stand for.
+///
+/// Unknown or malformed references are left untouched.
+pub(crate) fn unescape_lenient(content: &str) -> Cow<'_, str> {
+    if !content.contains('&') {
+        return Cow::Borrowed(content);
+    }
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(i) = rest.find('&') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i..];
+        match reference_length(rest)
+            .and_then(|length| decode_reference(&rest[1..length - 1]).map(|c| (c, length)))
+        {
+            Some((c, length)) => {
+                result.push(c);
+                rest = &rest[length..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    Cow::Owned(result)
+}
+
+fn escape_with<F>(content: &str, replacement: F) -> Cow<'_, str>
+where
+    F: Fn(&str, usize) -> Option<&'static str>,
+{
+    let mut result = String::new();
+    let mut last = 0;
+    for (i, _) in content.char_indices() {
+        if let Some(replacement) = replacement(content, i) {
+            result.push_str(&content[last..i]);
+            result.push_str(replacement);
+            last = i + 1;
+        }
+    }
+    if last == 0 {
+        return Cow::Borrowed(content);
+    }
+    result.push_str(&content[last..]);
+    Cow::Owned(result)
+}
+
+fn decode_reference(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let code = if let Some(code) = name.strip_prefix("#x") {
+                u32::from_str_radix(code, 16).ok()?
+            } else if let Some(code) = name.strip_prefix('#') {
+                code.parse().ok()?
+            } else {
+                return None;
+            };
+            std::char::from_u32(code)
+        }
+    }
+}
+
+// https://www.w3.org/TR/REC-xml/#NT-Reference
+fn reference_length(content: &str) -> Option<usize> {
+    let end = content.find(';')?;
+    let name = &content[1..end];
+    let valid = if let Some(code) = name.strip_prefix("#x") {
+        !code.is_empty() && code.chars().all(|c| c.is_ascii_hexdigit())
+    } else if let Some(code) = name.strip_prefix('#') {
+        !code.is_empty() && code.chars().all(|c| c.is_ascii_digit())
+    } else {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_' || c == ':')
+            && chars.all(|c| c.is_alphanumeric() || "_:-.".contains(c))
+    };
+    if valid && content.starts_with('&') {
+        Some(end + 1)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{escape, escape_attribute, escape_text, unescape, unescape_lenient};
+
+    #[test]
+    fn text_escaping() {
+        assert!(matches!(escape_text("foo"), Cow::Borrowed("foo")));
+        assert_eq!(escape_text("a < b && c"), "a &lt; b &amp;&amp; c");
+        assert_eq!(escape_text("&amp; &#42; &foo;"), "&amp; &#42; &foo;");
+        assert_eq!(escape_text("a ]]> b > c"), "a ]]&gt; b > c");
+    }
+
+    #[test]
+    fn attribute_escaping() {
+        assert_eq!(escape_attribute(r#"a "b" 'c'"#, '"'), "a &quot;b&quot; 'c'");
+        assert_eq!(
+            escape_attribute(r#"a "b" 'c'"#, '\''),
+            r#"a "b" &apos;c&apos;"#
+        );
+        assert_eq!(escape_attribute("&amp; <", '"'), "&amp;amp; &lt;");
+    }
+
+    #[test]
+    fn escaping() {
+        assert!(matches!(escape("foo bar"), Cow::Borrowed("foo bar")));
+        assert_eq!(
+            escape(r#"<a href="x">&amp; 'b'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;amp; &apos;b&apos;&lt;/a&gt;",
+        );
+        for content in ["foo", "a < b && c > d", r#""'"'"#, "&amp;", "é & ü"].iter() {
+            assert_eq!(unescape(&escape(content)).unwrap(), *content);
+        }
+    }
+
+    #[test]
+    fn unescaping() {
+        assert!(matches!(unescape("foo"), Ok(Cow::Borrowed("foo"))));
+        assert_eq!(unescape("&lt;&gt;&amp;&quot;&apos;").unwrap(), r#"<>&"'"#);
+        assert_eq!(unescape("&#42;&#x2A;").unwrap(), "**");
+
+        let error = unescape("a\nbé &foo;").unwrap_err();
+        assert_eq!(
+            (error.line(), error.column(), error.offset()),
+            (2, 4, Some(6))
+        );
+        assert!(unescape("a & b").is_err());
+        assert!(unescape("&#xD800;").is_err());
+
+        assert!(matches!(unescape_lenient("foo"), Cow::Borrowed("foo")));
+        assert_eq!(unescape_lenient("&lt;&gt;&amp;&quot;&apos;"), r#"<>&"'"#);
+        assert_eq!(unescape_lenient("&#42;&#x2A;"), "**");
+        assert_eq!(unescape_lenient("&foo; & &#xZZ;"), "&foo; & &#xZZ;");
+    }
+}